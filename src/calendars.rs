@@ -0,0 +1,3 @@
+pub mod fixed_calendar;
+pub mod offset_calendar;
+pub mod system_calendar;
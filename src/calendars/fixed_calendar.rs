@@ -0,0 +1,39 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+
+use crate::greeter_service::Calendar;
+
+pub struct FixedCalendar {
+    today: NaiveDate,
+    now: Option<DateTime<Tz>>,
+}
+
+impl FixedCalendar {
+    pub fn new(today: NaiveDate) -> Self {
+        Self { today, now: None }
+    }
+
+    pub fn at(now: DateTime<Tz>) -> Self {
+        Self {
+            today: now.date_naive(),
+            now: Some(now),
+        }
+    }
+}
+
+impl Calendar for FixedCalendar {
+    fn today(&self) -> NaiveDate {
+        self.today
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        match self.now {
+            Some(now) => now.with_timezone(&Utc),
+            None => self.today.and_time(NaiveTime::MIN).and_utc(),
+        }
+    }
+
+    fn time_zone(&self) -> Tz {
+        self.now.map_or(Tz::UTC, |now| now.timezone())
+    }
+}
@@ -0,0 +1,34 @@
+use std::rc::Rc;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+
+use crate::greeter_service::Calendar;
+
+pub struct OffsetCalendar {
+    calendar: Rc<dyn Calendar>,
+    offset_days: i64,
+}
+
+impl OffsetCalendar {
+    pub fn new(calendar: Rc<impl Calendar + 'static>, offset_days: i64) -> Self {
+        Self {
+            calendar,
+            offset_days,
+        }
+    }
+}
+
+impl Calendar for OffsetCalendar {
+    fn today(&self) -> NaiveDate {
+        self.calendar.today() + Duration::days(self.offset_days)
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        self.calendar.now() + Duration::days(self.offset_days)
+    }
+
+    fn time_zone(&self) -> Tz {
+        self.calendar.time_zone()
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+
+use crate::greeter_service::Calendar;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownTimeZoneError {
+    pub time_zone: String,
+}
+
+impl Display for UnknownTimeZoneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown time zone '{}'", self.time_zone)
+    }
+}
+
+impl std::error::Error for UnknownTimeZoneError {}
+
+pub struct SystemCalendar {
+    time_zone: Tz,
+}
+
+impl SystemCalendar {
+    pub fn new(time_zone: Tz) -> Self {
+        Self { time_zone }
+    }
+
+    pub fn from_time_zone_name(time_zone: &str) -> Result<Self, UnknownTimeZoneError> {
+        time_zone
+            .parse()
+            .map(Self::new)
+            .map_err(|_| UnknownTimeZoneError {
+                time_zone: time_zone.to_owned(),
+            })
+    }
+
+    pub fn time_zone(&self) -> Tz {
+        self.time_zone
+    }
+
+    pub fn date_at(&self, instant: DateTime<Utc>) -> NaiveDate {
+        instant.with_timezone(&self.time_zone).date_naive()
+    }
+}
+
+impl Default for SystemCalendar {
+    fn default() -> Self {
+        Self::new(Tz::UTC)
+    }
+}
+
+impl Calendar for SystemCalendar {
+    fn today(&self) -> NaiveDate {
+        self.date_at(Utc::now())
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn time_zone(&self) -> Tz {
+        self.time_zone
+    }
+}
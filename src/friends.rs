@@ -1,5 +1,15 @@
+pub mod birthdate;
+pub mod birthdate_parser;
+pub mod composite_friends_gateway;
+mod csv_reader;
 pub mod flat_file_friends_gateway;
 pub(crate) mod friend;
 pub mod friend_data;
 pub mod friends_gateway;
+mod friends_reader;
 pub(crate) mod friends_repository;
+pub mod in_memory_friends_gateway;
+pub mod json_friends_gateway;
+pub mod sqlite_friends_gateway;
+pub mod vcard_friends_gateway;
+pub mod writable_friends_repository;
@@ -0,0 +1,59 @@
+use chrono::{Datelike, NaiveDate};
+
+const LEAP_YEAR: i32 = 2000;
+
+// When friends born on February 29th celebrate in common years
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeapDayPolicy {
+    February28,
+    March1,
+    Skip,
+}
+
+#[derive(Eq, Hash, PartialEq, Debug, Clone, Copy)]
+pub struct Birthdate {
+    year: Option<i32>,
+    month: u32,
+    day: u32,
+}
+
+impl Birthdate {
+    pub fn from_ymd_opt(year: i32, month: u32, day: u32) -> Option<Self> {
+        NaiveDate::from_ymd_opt(year, month, day).map(Self::from)
+    }
+
+    pub fn from_md_opt(month: u32, day: u32) -> Option<Self> {
+        NaiveDate::from_ymd_opt(LEAP_YEAR, month, day).map(|_| Self {
+            year: None,
+            month,
+            day,
+        })
+    }
+
+    pub fn year(&self) -> Option<i32> {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    pub fn date(&self) -> Option<NaiveDate> {
+        self.year
+            .and_then(|year| NaiveDate::from_ymd_opt(year, self.month, self.day))
+    }
+}
+
+impl From<NaiveDate> for Birthdate {
+    fn from(date: NaiveDate) -> Self {
+        Self {
+            year: Some(date.year()),
+            month: date.month(),
+            day: date.day(),
+        }
+    }
+}
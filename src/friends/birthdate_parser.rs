@@ -0,0 +1,172 @@
+use chrono::{Datelike, NaiveDate};
+
+use super::{birthdate::Birthdate, friends_gateway::InvalidFriendRecord};
+
+const LEAP_YEAR: i32 = 2000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DateFormat {
+    pattern: String,
+}
+
+impl DateFormat {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.to_owned(),
+        }
+    }
+
+    pub fn iso() -> Self {
+        Self::new("%Y-%m-%d")
+    }
+
+    pub fn european() -> Self {
+        Self::new("%d/%m/%Y")
+    }
+
+    pub fn us() -> Self {
+        Self::new("%m/%d/%Y")
+    }
+
+    pub fn dotted() -> Self {
+        Self::new("%d.%m.%Y")
+    }
+
+    pub fn day_month() -> Self {
+        Self::new("%d/%m")
+    }
+
+    pub fn iso_month_day() -> Self {
+        Self::new("--%m-%d")
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn has_year(&self) -> bool {
+        self.pattern.contains("%Y") || self.pattern.contains("%y")
+    }
+
+    pub fn format(&self, birthdate: &Birthdate) -> Option<String> {
+        let date = match birthdate.year() {
+            Some(_) if self.has_year() => birthdate.date()?,
+            None if !self.has_year() => {
+                NaiveDate::from_ymd_opt(LEAP_YEAR, birthdate.month(), birthdate.day())?
+            }
+            _ => return None,
+        };
+        Some(date.format(&self.pattern).to_string())
+    }
+
+    fn parse(&self, value: &str) -> Option<Birthdate> {
+        if self.has_year() {
+            return NaiveDate::parse_from_str(value, &self.pattern)
+                .ok()
+                .map(Birthdate::from);
+        }
+        // chrono cannot parse a date without a year, so a leap one is borrowed to accept Feb 29
+        NaiveDate::parse_from_str(
+            &format!("{}|{}", value, LEAP_YEAR),
+            &format!("{}|%Y", self.pattern),
+        )
+        .ok()
+        .and_then(|date| Birthdate::from_md_opt(date.month(), date.day()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedBirthdate {
+    pub birthdate: Birthdate,
+    pub format: DateFormat,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BirthdateParseError {
+    Unrecognized,
+    Ambiguous(Vec<DateFormat>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BirthdateParser {
+    formats: Vec<DateFormat>,
+    pinned_format: Option<DateFormat>,
+}
+
+impl BirthdateParser {
+    pub fn new(formats: Vec<DateFormat>) -> Self {
+        Self {
+            formats,
+            pinned_format: None,
+        }
+    }
+
+    pub fn configure_pinned_format(&mut self, format: DateFormat) {
+        self.pinned_format = Some(format)
+    }
+
+    pub fn parse(&self, value: &str) -> Result<ParsedBirthdate, BirthdateParseError> {
+        let value = value.trim();
+        if let Some(format) = &self.pinned_format {
+            if let Some(date) = format.parse(value) {
+                return Ok(ParsedBirthdate {
+                    birthdate: date,
+                    format: format.clone(),
+                });
+            }
+        }
+
+        let matches: Vec<(Birthdate, &DateFormat)> = self
+            .formats
+            .iter()
+            .filter_map(|format| format.parse(value).map(|date| (date, format)))
+            .collect();
+        match matches.first() {
+            None => Err(BirthdateParseError::Unrecognized),
+            Some((date, _)) if matches.iter().any(|(other, _)| other != date) => Err(
+                BirthdateParseError::Ambiguous(matches.iter().map(|(_, f)| (*f).clone()).collect()),
+            ),
+            Some((date, format)) => Ok(ParsedBirthdate {
+                birthdate: *date,
+                format: (*format).clone(),
+            }),
+        }
+    }
+
+    pub fn format(&self, birthdate: &Birthdate) -> String {
+        self.pinned_format
+            .iter()
+            .chain(&self.formats)
+            .filter_map(|format| format.format(birthdate))
+            // A written date must read back as the same birthdate, so ambiguous ones are skipped
+            .find(|value| {
+                self.parse(value)
+                    .is_ok_and(|parsed| parsed.birthdate == *birthdate)
+            })
+            .unwrap_or_else(|| match birthdate.year() {
+                Some(_) => DateFormat::iso().format(birthdate).unwrap_or_default(),
+                None => DateFormat::iso_month_day()
+                    .format(birthdate)
+                    .unwrap_or_default(),
+            })
+    }
+
+    pub(crate) fn parse_field(&self, value: &str) -> Result<ParsedBirthdate, InvalidFriendRecord> {
+        self.parse(value).map_err(|error| match error {
+            BirthdateParseError::Unrecognized => InvalidFriendRecord::InvalidDate(value.to_owned()),
+            BirthdateParseError::Ambiguous(_) => {
+                InvalidFriendRecord::AmbiguousDate(value.to_owned())
+            }
+        })
+    }
+}
+
+impl Default for BirthdateParser {
+    fn default() -> Self {
+        Self::new(vec![
+            DateFormat::european(),
+            DateFormat::day_month(),
+            DateFormat::iso_month_day(),
+        ])
+    }
+}
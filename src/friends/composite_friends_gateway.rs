@@ -0,0 +1,184 @@
+use std::{collections::HashMap, rc::Rc};
+
+use super::{
+    friend_data::FriendData,
+    friends_gateway::{FriendsGateway, FriendsGatewayError, FriendsLoadReport, ResolvedConflict},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolutionPolicy {
+    FirstWins,
+    MostRecentWins,
+    MergeFields,
+}
+
+pub struct CompositeFriendsGateway {
+    friends_gateways: Vec<Rc<dyn FriendsGateway>>,
+    conflict_resolution_policy: ConflictResolutionPolicy,
+}
+
+impl CompositeFriendsGateway {
+    pub fn new(friends_gateways: Vec<Rc<dyn FriendsGateway>>) -> Self {
+        Self {
+            friends_gateways,
+            conflict_resolution_policy: ConflictResolutionPolicy::FirstWins,
+        }
+    }
+
+    pub fn configure_conflict_resolution_policy(&mut self, policy: ConflictResolutionPolicy) {
+        self.conflict_resolution_policy = policy
+    }
+
+    fn resolve(&self, duplicates: &[&SourcedFriend]) -> FriendData {
+        match self.conflict_resolution_policy {
+            ConflictResolutionPolicy::FirstWins => duplicates[0].friend.clone(),
+            ConflictResolutionPolicy::MostRecentWins => duplicates
+                .iter()
+                .max_by_key(|d| (d.last_modified, d.source))
+                .map(|d| d.friend.clone())
+                .unwrap(),
+            ConflictResolutionPolicy::MergeFields => merge_fields(duplicates),
+        }
+    }
+}
+
+impl FriendsGateway for CompositeFriendsGateway {
+    fn get_friends(&self) -> Result<Vec<FriendData>, FriendsGatewayError> {
+        self.get_friends_with_report().map(|(friends, _)| friends)
+    }
+
+    fn get_friends_with_report(
+        &self,
+    ) -> Result<(Vec<FriendData>, FriendsLoadReport), FriendsGatewayError> {
+        let mut report = FriendsLoadReport::default();
+        let mut sourced_friends = Vec::new();
+        for (source, friends_gateway) in self.friends_gateways.iter().enumerate() {
+            let (friends, source_report) = friends_gateway.get_friends_with_report()?;
+            let last_modified = friends_gateway.last_modified();
+            sourced_friends.extend(friends.into_iter().map(|friend| SourcedFriend {
+                source,
+                last_modified,
+                friend,
+            }));
+            report
+                .rejected_records
+                .extend(source_report.rejected_records);
+            report
+                .resolved_conflicts
+                .extend(source_report.resolved_conflicts);
+            report
+                .matched_date_formats
+                .extend(source_report.matched_date_formats);
+        }
+
+        let mut friends = Vec::new();
+        for duplicates in group_duplicates(&sourced_friends) {
+            let resolved = self.resolve(&duplicates);
+            if duplicates.len() > 1 {
+                report.resolved_conflicts.push(ResolvedConflict::new(
+                    resolved.clone(),
+                    duplicates.iter().map(|d| d.friend.clone()).collect(),
+                ));
+            }
+            friends.push(resolved);
+        }
+        Ok((friends, report))
+    }
+}
+
+struct SourcedFriend {
+    source: usize,
+    last_modified: Option<std::time::SystemTime>,
+    friend: FriendData,
+}
+
+fn identity_keys(friend: &FriendData) -> Vec<String> {
+    let mut keys = Vec::new();
+    let email = friend.email.trim().to_lowercase();
+    if !email.is_empty() {
+        keys.push(format!("email:{}", email));
+    }
+    let phone_number: String = friend
+        .phone_number
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect();
+    if !phone_number.is_empty() {
+        keys.push(format!("phone:{}", phone_number));
+    }
+    // The birth year is left out, some sources only know the day and month of the birthday
+    keys.push(format!(
+        "name:{}|{}|{:02}-{:02}",
+        friend.name.trim().to_lowercase(),
+        friend.surname.trim().to_lowercase(),
+        friend.birthdate.month(),
+        friend.birthdate.day()
+    ));
+    keys
+}
+
+fn group_duplicates(sourced_friends: &[SourcedFriend]) -> Vec<Vec<&SourcedFriend>> {
+    let mut groups: Vec<usize> = (0..sourced_friends.len()).collect();
+    fn root(groups: &mut [usize], index: usize) -> usize {
+        let mut index = index;
+        while groups[index] != index {
+            groups[index] = groups[groups[index]];
+            index = groups[index];
+        }
+        index
+    }
+
+    let mut first_by_key: HashMap<String, usize> = HashMap::new();
+    for (index, sourced_friend) in sourced_friends.iter().enumerate() {
+        for key in identity_keys(&sourced_friend.friend) {
+            let first = *first_by_key.entry(key).or_insert(index);
+            let (first_root, index_root) = (root(&mut groups, first), root(&mut groups, index));
+            groups[index_root.max(first_root)] = index_root.min(first_root);
+        }
+    }
+
+    let mut duplicates: Vec<Vec<&SourcedFriend>> = Vec::new();
+    let mut group_positions: HashMap<usize, usize> = HashMap::new();
+    for (index, sourced_friend) in sourced_friends.iter().enumerate() {
+        let group = root(&mut groups, index);
+        match group_positions.get(&group) {
+            Some(position) => duplicates[*position].push(sourced_friend),
+            None => {
+                group_positions.insert(group, duplicates.len());
+                duplicates.push(vec![sourced_friend]);
+            }
+        }
+    }
+    duplicates
+}
+
+fn merge_fields(duplicates: &[&SourcedFriend]) -> FriendData {
+    let mut merged = duplicates[0].friend.clone();
+    for duplicate in &duplicates[1..] {
+        let friend = &duplicate.friend;
+        for (field, value) in [
+            (&mut merged.name, &friend.name),
+            (&mut merged.surname, &friend.surname),
+            (&mut merged.email, &friend.email),
+            (&mut merged.phone_number, &friend.phone_number),
+        ] {
+            if field.trim().is_empty() {
+                field.clone_from(value);
+            }
+        }
+        if merged.locale.is_none() {
+            merged.locale = friend.locale.clone();
+        }
+        if merged.time_zone.is_none() {
+            merged.time_zone = friend.time_zone;
+        }
+        if merged.birthdate.year().is_none()
+            && friend.birthdate.year().is_some()
+            && merged.birthdate.month() == friend.birthdate.month()
+            && merged.birthdate.day() == friend.birthdate.day()
+        {
+            merged.birthdate = friend.birthdate;
+        }
+    }
+    merged
+}
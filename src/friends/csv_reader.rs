@@ -0,0 +1,150 @@
+use super::friends_gateway::InvalidFriendRecord;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+pub(crate) struct CsvRecord {
+    pub(crate) line: usize,
+    pub(crate) content: String,
+    pub(crate) fields: Result<Vec<String>, InvalidFriendRecord>,
+}
+
+pub(crate) fn read_records(content: &[u8]) -> Vec<CsvRecord> {
+    let content = content.strip_prefix(UTF8_BOM).unwrap_or(content);
+    let mut reader = CsvReader {
+        content,
+        position: 0,
+        line: 1,
+    };
+    let mut records = Vec::new();
+    while reader.position < content.len() {
+        let line = reader.line;
+        let start = reader.position;
+        let fields = reader.read_fields();
+        let end = reader.position;
+        let raw_record = trim_line_ending(&content[start..end]);
+        if raw_record.trim_ascii().is_empty() {
+            continue;
+        }
+        records.push(CsvRecord {
+            line,
+            content: String::from_utf8_lossy(raw_record).into_owned(),
+            fields: fields.and_then(|fields| {
+                fields
+                    .into_iter()
+                    .map(|f| String::from_utf8(f).map_err(|_| InvalidFriendRecord::InvalidEncoding))
+                    .collect()
+            }),
+        });
+    }
+    records
+}
+
+fn trim_line_ending(record: &[u8]) -> &[u8] {
+    let record = record.strip_suffix(b"\n").unwrap_or(record);
+    record.strip_suffix(b"\r").unwrap_or(record)
+}
+
+struct CsvReader<'a> {
+    content: &'a [u8],
+    position: usize,
+    line: usize,
+}
+
+impl CsvReader<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.content.get(self.position).copied()
+    }
+
+    fn read_fields(&mut self) -> Result<Vec<Vec<u8>>, InvalidFriendRecord> {
+        let mut fields = Vec::new();
+        let mut error = None;
+        loop {
+            self.skip_blanks();
+            let field = if self.peek() == Some(b'"') {
+                self.read_quoted_field()
+            } else {
+                Ok(self.read_unquoted_field())
+            };
+            match field {
+                Ok(field) => fields.push(field),
+                Err(e) => {
+                    error.get_or_insert(e);
+                    self.skip_to_field_end();
+                }
+            }
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                _ => break,
+            }
+        }
+        self.read_line_ending();
+        match error {
+            Some(error) => Err(error),
+            None => Ok(fields),
+        }
+    }
+
+    fn read_unquoted_field(&mut self) -> Vec<u8> {
+        let start = self.position;
+        self.skip_to_field_end();
+        self.content[start..self.position].trim_ascii().to_vec()
+    }
+
+    fn read_quoted_field(&mut self) -> Result<Vec<u8>, InvalidFriendRecord> {
+        let mut field = Vec::new();
+        self.position += 1;
+        loop {
+            match self.peek() {
+                None => {
+                    return Err(InvalidFriendRecord::MalformedCsv(
+                        "unterminated quoted field".to_owned(),
+                    ))
+                }
+                Some(b'"') if self.content.get(self.position + 1) == Some(&b'"') => {
+                    field.push(b'"');
+                    self.position += 2;
+                }
+                Some(b'"') => {
+                    self.position += 1;
+                    break;
+                }
+                Some(c) => {
+                    if c == b'\n' {
+                        self.line += 1;
+                    }
+                    field.push(c);
+                    self.position += 1;
+                }
+            }
+        }
+        self.skip_blanks();
+        match self.peek() {
+            None | Some(b',') | Some(b'\r') | Some(b'\n') => Ok(field),
+            Some(_) => Err(InvalidFriendRecord::MalformedCsv(
+                "unexpected character after quoted field".to_owned(),
+            )),
+        }
+    }
+
+    fn skip_blanks(&mut self) {
+        while matches!(self.peek(), Some(b' ') | Some(b'\t')) {
+            self.position += 1;
+        }
+    }
+
+    fn skip_to_field_end(&mut self) {
+        while !matches!(self.peek(), None | Some(b',') | Some(b'\r') | Some(b'\n')) {
+            self.position += 1;
+        }
+    }
+
+    fn read_line_ending(&mut self) {
+        if self.peek() == Some(b'\r') {
+            self.position += 1;
+        }
+        if self.peek() == Some(b'\n') {
+            self.position += 1;
+        }
+        self.line += 1;
+    }
+}
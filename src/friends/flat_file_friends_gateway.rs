@@ -1,38 +1,427 @@
 use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+    collections::{HashMap, HashSet},
+    fs::{self, File},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    process,
+    time::SystemTime,
 };
 
-use super::{friend_data::FriendData, friends_gateway::FriendsGateway};
-use chrono::NaiveDate;
+use super::{
+    birthdate_parser::{BirthdateParser, DateFormat, ParsedBirthdate},
+    csv_reader::{self, CsvRecord},
+    friend_data::{self, FriendData, FriendId},
+    friends_gateway::{
+        FriendsGateway, FriendsGatewayError, FriendsLoadReport, InvalidFriendRecord,
+        MatchedDateFormat, RejectedRecord, WritableFriendsGateway,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FriendColumn {
+    Id,
+    LastName,
+    FirstName,
+    DateOfBirth,
+    Email,
+    PhoneNumber,
+    TimeZone,
+    Locale,
+}
+
+impl FriendColumn {
+    const ALL: [FriendColumn; 8] = [
+        FriendColumn::Id,
+        FriendColumn::LastName,
+        FriendColumn::FirstName,
+        FriendColumn::DateOfBirth,
+        FriendColumn::Email,
+        FriendColumn::PhoneNumber,
+        FriendColumn::TimeZone,
+        FriendColumn::Locale,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FriendColumn::Id => "id",
+            FriendColumn::LastName => "last_name",
+            FriendColumn::FirstName => "first_name",
+            FriendColumn::DateOfBirth => "date_of_birth",
+            FriendColumn::Email => "email",
+            FriendColumn::PhoneNumber => "phone_number",
+            FriendColumn::TimeZone => "time_zone",
+            FriendColumn::Locale => "locale",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadingMode {
+    Strict,
+    Lenient,
+}
+
+enum FlatFile {
+    Handle(File),
+    Path(PathBuf),
+}
 
 pub struct FlatFileFriendsGateway {
-    file: File,
+    file: FlatFile,
+    loading_mode: LoadingMode,
+    column_mapping: HashMap<String, FriendColumn>,
+    birthdate_parser: BirthdateParser,
 }
 
 impl FlatFileFriendsGateway {
     pub fn new(file: File) -> Self {
-        Self { file }
+        Self::from_flat_file(FlatFile::Handle(file))
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Self {
+        Self::from_flat_file(FlatFile::Path(path.as_ref().to_owned()))
+    }
+
+    fn from_flat_file(file: FlatFile) -> Self {
+        Self {
+            file,
+            loading_mode: LoadingMode::Strict,
+            column_mapping: HashMap::new(),
+            birthdate_parser: BirthdateParser::default(),
+        }
+    }
+
+    pub fn configure_loading_mode(&mut self, loading_mode: LoadingMode) {
+        self.loading_mode = loading_mode
+    }
+
+    pub fn configure_column_mapping(&mut self, header: &str, column: FriendColumn) {
+        self.column_mapping.insert(normalize_header(header), column);
+    }
+
+    fn map_columns(&self, header: &[String]) -> ColumnLayout {
+        let mut column_indexes = HashMap::new();
+        for (index, header) in header.iter().enumerate() {
+            let header = normalize_header(header);
+            let column = self.column_mapping.get(&header).copied().or_else(|| {
+                FriendColumn::ALL
+                    .into_iter()
+                    .find(|column| column.name() == header)
+            });
+            if let Some(column) = column {
+                column_indexes.entry(column).or_insert(index);
+            }
+        }
+        ColumnLayout {
+            header: header.to_vec(),
+            column_indexes,
+        }
+    }
+
+    pub fn configure_birthdate_parser(&mut self, birthdate_parser: BirthdateParser) {
+        self.birthdate_parser = birthdate_parser
+    }
+
+    fn parse_friend(
+        &self,
+        layout: &ColumnLayout,
+        friend_data: &[String],
+    ) -> Result<(FriendData, DateFormat), InvalidFriendRecord> {
+        let field = |column: FriendColumn| layout.field(friend_data, column);
+        let surname = field(FriendColumn::LastName)?;
+        let name = field(FriendColumn::FirstName)?;
+        let ParsedBirthdate {
+            birthdate: birth_date,
+            format,
+        } = self
+            .birthdate_parser
+            .parse_field(field(FriendColumn::DateOfBirth)?)?;
+        let email = field(FriendColumn::Email)?;
+        let phone_number = field(FriendColumn::PhoneNumber)?;
+        let mut friend = FriendData::new(name, surname, birth_date, email, phone_number);
+        if let Ok(id) = field(FriendColumn::Id) {
+            if !id.is_empty() {
+                friend = friend.with_id(FriendId::new(id));
+            }
+        }
+        if let Ok(time_zone) = field(FriendColumn::TimeZone) {
+            friend.time_zone = friend_data::parse_time_zone(time_zone)?;
+        }
+        if let Ok(locale) = field(FriendColumn::Locale) {
+            friend.locale = friend_data::parse_locale(locale)?;
+        }
+        Ok((friend, format))
+    }
+
+    // Rows are always loaded strictly before a rewrite, a lenient load would drop the invalid ones
+    fn load_strictly(&self) -> Result<FlatFileRows, FriendsGatewayError> {
+        let (layout, records) = self.read_records()?;
+        let mut rows = Vec::new();
+        let mut ids = HashSet::new();
+        for CsvRecord { line, fields, .. } in records {
+            let invalid_record = |error| FriendsGatewayError::InvalidRecord { line, error };
+            let fields = fields.map_err(invalid_record)?;
+            let (friend, _) = self
+                .parse_friend(&layout, &fields)
+                .map_err(invalid_record)?;
+            if !ids.insert(friend.id.clone()) {
+                return Err(invalid_record(InvalidFriendRecord::InvalidField(
+                    FriendColumn::Id.name().to_owned(),
+                )));
+            }
+            rows.push((friend, fields));
+        }
+        Ok(FlatFileRows { layout, rows })
+    }
+
+    fn read_records(&self) -> Result<(ColumnLayout, Vec<CsvRecord>), FriendsGatewayError> {
+        let content = match &self.file {
+            FlatFile::Handle(file) => {
+                // Rewind, as a long-lived service loads friends again at each run
+                let mut content = Vec::new();
+                (&*file)
+                    .seek(SeekFrom::Start(0))
+                    .and_then(|_| (&*file).read_to_end(&mut content))
+                    .map(|_| content)
+            }
+            FlatFile::Path(path) => fs::read(path),
+        }
+        .map_err(|e| FriendsGatewayError::Io(e.to_string()))?;
+        let mut records = csv_reader::read_records(&content).into_iter();
+        let layout = match records.next() {
+            Some(CsvRecord { line, fields, .. }) => self.map_columns(
+                &fields.map_err(|error| FriendsGatewayError::InvalidRecord { line, error })?,
+            ),
+            None => ColumnLayout {
+                header: Vec::new(),
+                column_indexes: HashMap::new(),
+            },
+        };
+        Ok((layout, records.collect()))
+    }
+
+    // The header keeps its names, order and unknown columns, whose values each row keeps too
+    fn write_rows(&self, rows: &FlatFileRows) -> Result<(), FriendsGatewayError> {
+        let FlatFile::Path(path) = &self.file else {
+            return Err(FriendsGatewayError::Write(
+                "the flat file was not opened from a path".to_owned(),
+            ));
+        };
+        let columns = rows.columns();
+        let header: Vec<&str> = columns
+            .iter()
+            .map(|column| match column {
+                WrittenColumn::Friend(_, Some(index)) | WrittenColumn::Unknown(index) => {
+                    rows.layout.header[*index].trim()
+                }
+                WrittenColumn::Friend(column, None) => column.name(),
+            })
+            .collect();
+        let mut content = format_record(&header);
+        for (friend, original_fields) in &rows.rows {
+            let birthdate = self.birthdate_parser.format(&friend.birthdate);
+            let time_zone = friend.time_zone.map(|tz| tz.name()).unwrap_or_default();
+            let locale = friend
+                .locale
+                .as_ref()
+                .map(friend_data::Locale::as_str)
+                .unwrap_or_default();
+            let fields: Vec<&str> = columns
+                .iter()
+                .map(|column| match column {
+                    WrittenColumn::Friend(FriendColumn::Id, _) => friend.id.as_str(),
+                    WrittenColumn::Friend(FriendColumn::LastName, _) => &friend.surname,
+                    WrittenColumn::Friend(FriendColumn::FirstName, _) => &friend.name,
+                    WrittenColumn::Friend(FriendColumn::DateOfBirth, _) => &birthdate,
+                    WrittenColumn::Friend(FriendColumn::Email, _) => &friend.email,
+                    WrittenColumn::Friend(FriendColumn::PhoneNumber, _) => &friend.phone_number,
+                    WrittenColumn::Friend(FriendColumn::TimeZone, _) => time_zone,
+                    WrittenColumn::Friend(FriendColumn::Locale, _) => locale,
+                    WrittenColumn::Unknown(index) => original_fields
+                        .get(*index)
+                        .map(String::as_str)
+                        .unwrap_or_default(),
+                })
+                .collect();
+            content.push_str(&format_record(&fields));
+        }
+
+        // The new content is written aside and renamed over the file, so readers never see half of it
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let temp_path = path.with_file_name(format!(".{}.{}.tmp", file_name, process::id()));
+        let written = File::create(&temp_path)
+            .and_then(|mut temp_file| {
+                temp_file.write_all(content.as_bytes())?;
+                temp_file.sync_all()
+            })
+            .and_then(|_| fs::rename(&temp_path, path));
+        if let Err(e) = written {
+            let _ = fs::remove_file(&temp_path);
+            return Err(FriendsGatewayError::Write(e.to_string()));
+        }
+        Ok(())
     }
 }
 
 impl FriendsGateway for FlatFileFriendsGateway {
-    fn get_friends(&self) -> Vec<FriendData> {
-        let reader = BufReader::new(&self.file);
-        reader
-            .lines()
-            .skip(1)
-            .map(|l| {
-                let friend_line = l.unwrap();
-                let friend_data: Vec<&str> = friend_line.trim().split(',').collect();
-                let surname = friend_data[0].trim();
-                let name = friend_data[1].trim();
-                let birth_date =
-                    NaiveDate::parse_from_str(friend_data[2].trim(), "%d/%m/%Y").unwrap();
-                let email = friend_data[3].trim();
-                let phone_number = friend_data[4].trim();
-                FriendData::new(name, surname, birth_date, email, phone_number)
+    fn get_friends(&self) -> Result<Vec<FriendData>, FriendsGatewayError> {
+        self.get_friends_with_report().map(|(friends, _)| friends)
+    }
+
+    fn get_friends_with_report(
+        &self,
+    ) -> Result<(Vec<FriendData>, FriendsLoadReport), FriendsGatewayError> {
+        let (layout, records) = self.read_records()?;
+        let mut friends = Vec::new();
+        let mut rejected_records = Vec::new();
+        let mut matched_date_formats = Vec::new();
+        for CsvRecord {
+            line,
+            content,
+            fields,
+        } in records
+        {
+            match fields.and_then(|fields| self.parse_friend(&layout, &fields)) {
+                Ok((friend, format)) => {
+                    friends.push(friend);
+                    matched_date_formats.push(MatchedDateFormat::new(line, format));
+                }
+                Err(error) if self.loading_mode == LoadingMode::Lenient => {
+                    rejected_records.push(RejectedRecord::new(line, &content, error))
+                }
+                Err(error) => return Err(FriendsGatewayError::InvalidRecord { line, error }),
+            }
+        }
+        Ok((
+            friends,
+            FriendsLoadReport::new(rejected_records)
+                .with_matched_date_formats(matched_date_formats),
+        ))
+    }
+
+    fn last_modified(&self) -> Option<SystemTime> {
+        match &self.file {
+            FlatFile::Handle(file) => file.metadata(),
+            FlatFile::Path(path) => fs::metadata(path),
+        }
+        .and_then(|m| m.modified())
+        .ok()
+    }
+}
+
+impl WritableFriendsGateway for FlatFileFriendsGateway {
+    fn add_friend(&self, friend: FriendData) -> Result<FriendId, FriendsGatewayError> {
+        let mut rows = self.load_strictly()?;
+        let last_id = rows
+            .rows
+            .iter()
+            .filter_map(|(friend, _)| friend.id.as_str().parse::<u64>().ok())
+            .max()
+            .unwrap_or(0);
+        let id = FriendId::new(&(last_id + 1).to_string());
+        rows.rows.push((friend.with_id(id.clone()), Vec::new()));
+        self.write_rows(&rows)?;
+        Ok(id)
+    }
+
+    fn update_friend(&self, id: &FriendId, friend: FriendData) -> Result<(), FriendsGatewayError> {
+        let mut rows = self.load_strictly()?;
+        let (updated, _) = rows
+            .rows
+            .iter_mut()
+            .find(|(friend, _)| friend.id == *id)
+            .ok_or_else(|| FriendsGatewayError::FriendNotFound(id.clone()))?;
+        *updated = friend.with_id(id.clone());
+        self.write_rows(&rows)
+    }
+
+    fn remove_friend(&self, id: &FriendId) -> Result<(), FriendsGatewayError> {
+        let mut rows = self.load_strictly()?;
+        let position = rows
+            .rows
+            .iter()
+            .position(|(friend, _)| friend.id == *id)
+            .ok_or_else(|| FriendsGatewayError::FriendNotFound(id.clone()))?;
+        rows.rows.remove(position);
+        self.write_rows(&rows)
+    }
+}
+
+struct ColumnLayout {
+    header: Vec<String>,
+    column_indexes: HashMap<FriendColumn, usize>,
+}
+
+impl ColumnLayout {
+    fn field<'a>(
+        &self,
+        friend_data: &'a [String],
+        column: FriendColumn,
+    ) -> Result<&'a str, InvalidFriendRecord> {
+        self.column_indexes
+            .get(&column)
+            .and_then(|index| friend_data.get(*index))
+            .map(String::as_str)
+            .ok_or_else(|| InvalidFriendRecord::MissingField(column.name().to_owned()))
+    }
+}
+
+struct FlatFileRows {
+    layout: ColumnLayout,
+    rows: Vec<(FriendData, Vec<String>)>,
+}
+
+enum WrittenColumn {
+    // The index of the column in the original header, if it was there
+    Friend(FriendColumn, Option<usize>),
+    Unknown(usize),
+}
+
+impl FlatFileRows {
+    // Columns friends need and the file lacks are added: the id first, the others last.
+    // Time zone and locale columns are only added when needed, to keep files without them unchanged
+    fn columns(&self) -> Vec<WrittenColumn> {
+        let mut columns: Vec<WrittenColumn> = (0..self.layout.header.len())
+            .map(|index| {
+                self.layout
+                    .column_indexes
+                    .iter()
+                    .find(|(_, column_index)| **column_index == index)
+                    .map_or(WrittenColumn::Unknown(index), |(column, _)| {
+                        WrittenColumn::Friend(*column, Some(index))
+                    })
             })
-            .collect()
+            .collect();
+        let friends = || self.rows.iter().map(|(friend, _)| friend);
+        for column in FriendColumn::ALL {
+            if self.layout.column_indexes.contains_key(&column) {
+                continue;
+            }
+            match column {
+                FriendColumn::Id => columns.insert(0, WrittenColumn::Friend(column, None)),
+                FriendColumn::TimeZone if friends().all(|f| f.time_zone.is_none()) => {}
+                FriendColumn::Locale if friends().all(|f| f.locale.is_none()) => {}
+                _ => columns.push(WrittenColumn::Friend(column, None)),
+            }
+        }
+        columns
     }
 }
+
+fn format_record(fields: &[&str]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.trim() != *field || field.contains([',', '"', '\r', '\n']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect();
+    format!("{}\n", fields.join(", "))
+}
+
+fn normalize_header(header: &str) -> String {
+    header.trim().to_lowercase()
+}
@@ -1,43 +1,89 @@
-use chrono::{Datelike, NaiveDate};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use chrono_tz::Tz;
 
-use super::friend_data::FriendData;
+use super::{
+    birthdate::{Birthdate, LeapDayPolicy},
+    friend_data::{FriendData, FriendId, Locale},
+};
 
 #[derive(Clone)]
 pub(crate) struct Friend {
+    pub(crate) id: FriendId,
     pub(crate) name: String,
     pub(crate) surname: String,
-    pub(crate) birthdate: NaiveDate,
+    pub(crate) birthdate: Birthdate,
     pub(crate) email: String,
     pub(crate) phone_number: String,
+    pub(crate) time_zone: Option<Tz>,
+    pub(crate) locale: Option<Locale>,
 }
 
 impl Friend {
     pub(crate) fn from(friend_data: &FriendData) -> Self {
         Self {
+            id: friend_data.id.clone(),
             name: friend_data.name.to_owned(),
             surname: friend_data.surname.to_owned(),
-            birthdate: friend_data.birthdate.to_owned(),
+            birthdate: friend_data.birthdate,
             email: friend_data.email.to_owned(),
             phone_number: friend_data.phone_number.to_owned(),
+            time_zone: friend_data.time_zone,
+            locale: friend_data.locale.clone(),
         }
     }
 
     pub(crate) fn to(friend: &Self) -> FriendData {
-        FriendData::new(
+        let mut friend_data = FriendData::new(
             &friend.name,
             &friend.surname,
             friend.birthdate,
             &friend.email,
             &friend.phone_number,
         )
+        .with_id(friend.id.clone());
+        friend_data.time_zone = friend.time_zone;
+        friend_data.locale = friend.locale.clone();
+        friend_data
     }
 
-    pub(crate) fn is_it_their_birthday(&self, date: NaiveDate) -> bool {
-        let birthday = self.birthdate;
-        birthday.month() == date.month() && self.birthdate.day() == date.day()
-            || date.month() == 2
-                && date.day() == 28
-                && birthday.month() == 2
-                && birthday.day() == 29
+    pub(crate) fn local_date(&self, instant: DateTime<Utc>, default_time_zone: Tz) -> NaiveDate {
+        instant
+            .with_timezone(&self.time_zone.unwrap_or(default_time_zone))
+            .date_naive()
+    }
+
+    // Unknown for friends whose birth year is unknown
+    pub(crate) fn age_on(&self, date: NaiveDate) -> Option<u32> {
+        let year = self.birthdate.year()?;
+        u32::try_from(date.year() - year).ok()
+    }
+
+    pub(crate) fn is_it_their_birthday(
+        &self,
+        date: NaiveDate,
+        leap_day_policy: LeapDayPolicy,
+    ) -> bool {
+        Self::birthdays_celebrated_on(date, leap_day_policy)
+            .contains(&(self.birthdate.month(), self.birthdate.day()))
+    }
+
+    pub(crate) fn birthdays_celebrated_on(
+        date: NaiveDate,
+        leap_day_policy: LeapDayPolicy,
+    ) -> Vec<(u32, u32)> {
+        let mut birthdays = vec![(date.month(), date.day())];
+        let is_leap_year = NaiveDate::from_ymd_opt(date.year(), 2, 29).is_some();
+        if is_leap_year {
+            return birthdays;
+        }
+        let leap_day_celebrated = match leap_day_policy {
+            LeapDayPolicy::February28 => (2, 28),
+            LeapDayPolicy::March1 => (3, 1),
+            LeapDayPolicy::Skip => return birthdays,
+        };
+        if birthdays[0] == leap_day_celebrated {
+            birthdays.push((2, 29));
+        }
+        birthdays
     }
 }
@@ -1,28 +1,179 @@
-use chrono::NaiveDate;
+use std::fmt::Display;
+
+use chrono_tz::Tz;
+
+use super::{birthdate::Birthdate, friends_gateway::InvalidFriendRecord};
+
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct FriendId(String);
+
+impl FriendId {
+    pub fn new(id: &str) -> Self {
+        Self(id.to_owned())
+    }
+
+    // Friends without an explicit id are keyed by who they are, so the key survives reloads.
+    // Their email, or else their phone number, tells apart homonyms born on the same day
+    pub fn derived(
+        name: &str,
+        surname: &str,
+        birthdate: &Birthdate,
+        email: &str,
+        phone_number: &str,
+    ) -> Self {
+        let birthday = match birthdate.year() {
+            Some(year) => format!("{:04}{:02}{:02}", year, birthdate.month(), birthdate.day()),
+            None => format!("{:02}{:02}", birthdate.month(), birthdate.day()),
+        };
+        let key = format!("{} {} {}", surname, name, birthday).to_lowercase();
+        let words: Vec<String> = key
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_owned)
+            .collect();
+        let contact = if email.trim().is_empty() {
+            phone_number
+        } else {
+            email
+        };
+        let contact: String = contact
+            .to_lowercase()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        match contact.as_str() {
+            "" => Self(words.join("-")),
+            contact => Self(format!("{}-{}", words.join("-"), contact)),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for FriendId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+// A language, optionally narrowed to a region: "it" or "it-CH"
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct Locale(String);
+
+impl Locale {
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split(['-', '_']);
+        let language = parts.next()?;
+        let region = parts.next();
+        let is_code = |code: &str, length| {
+            code.len() == length && code.chars().all(|c| c.is_ascii_alphabetic())
+        };
+        if parts.next().is_some() || !(is_code(language, 2) || is_code(language, 3)) {
+            return None;
+        }
+        match region {
+            None => Some(Self(language.to_lowercase())),
+            Some(region) if is_code(region, 2) => Some(Self(format!(
+                "{}-{}",
+                language.to_lowercase(),
+                region.to_uppercase()
+            ))),
+            Some(_) => None,
+        }
+    }
+
+    pub fn language(&self) -> &str {
+        self.0.split('-').next().unwrap_or_default()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    // "it-CH" falls back to "it"
+    pub fn fallbacks(&self) -> Vec<Locale> {
+        let mut fallbacks = vec![self.clone()];
+        if self.language() != self.0 {
+            fallbacks.push(Self(self.language().to_owned()));
+        }
+        fallbacks
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
 
 #[derive(Eq, Hash, PartialEq, Debug, Clone)]
 pub struct FriendData {
+    pub id: FriendId,
     pub name: String,
     pub surname: String,
-    pub birthdate: NaiveDate,
+    pub birthdate: Birthdate,
     pub email: String,
     pub phone_number: String,
+    pub time_zone: Option<Tz>,
+    pub locale: Option<Locale>,
 }
 
 impl FriendData {
     pub fn new(
         name: &str,
         surname: &str,
-        birthdate: NaiveDate,
+        birthdate: impl Into<Birthdate>,
         email: &str,
         phone_number: &str,
     ) -> Self {
+        let birthdate = birthdate.into();
         Self {
+            id: FriendId::derived(name, surname, &birthdate, email, phone_number),
             name: name.to_owned(),
             surname: surname.to_owned(),
             birthdate,
             email: email.to_owned(),
             phone_number: phone_number.to_owned(),
+            time_zone: None,
+            locale: None,
         }
     }
+
+    pub fn with_id(mut self, id: FriendId) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn with_time_zone(mut self, time_zone: Tz) -> Self {
+        self.time_zone = Some(time_zone);
+        self
+    }
+
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+}
+
+pub(crate) fn parse_time_zone(value: &str) -> Result<Option<Tz>, InvalidFriendRecord> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| InvalidFriendRecord::InvalidTimeZone(value.to_owned()))
+}
+
+pub(crate) fn parse_locale(value: &str) -> Result<Option<Locale>, InvalidFriendRecord> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    Locale::parse(value)
+        .map(Some)
+        .ok_or_else(|| InvalidFriendRecord::InvalidLocale(value.to_owned()))
 }
@@ -1,5 +1,186 @@
-use super::friend_data::FriendData;
+use std::{fmt::Display, time::SystemTime};
+
+use super::{
+    birthdate_parser::DateFormat,
+    friend_data::{FriendData, FriendId},
+};
 
 pub trait FriendsGateway {
-    fn get_friends(&self) -> Vec<FriendData>;
+    fn get_friends(&self) -> Result<Vec<FriendData>, FriendsGatewayError>;
+
+    fn get_friends_with_report(
+        &self,
+    ) -> Result<(Vec<FriendData>, FriendsLoadReport), FriendsGatewayError> {
+        Ok((self.get_friends()?, FriendsLoadReport::default()))
+    }
+
+    fn get_friends_born_on(
+        &self,
+        month_days: &[(u32, u32)],
+    ) -> Result<(Vec<FriendData>, FriendsLoadReport), FriendsGatewayError> {
+        let (friends, report) = self.get_friends_with_report()?;
+        let friends = friends
+            .into_iter()
+            .filter(|f| month_days.contains(&(f.birthdate.month(), f.birthdate.day())))
+            .collect();
+        Ok((friends, report))
+    }
+
+    fn last_modified(&self) -> Option<SystemTime> {
+        None
+    }
+}
+
+pub trait WritableFriendsGateway: FriendsGateway {
+    fn add_friend(&self, friend: FriendData) -> Result<FriendId, FriendsGatewayError>;
+    fn update_friend(&self, id: &FriendId, friend: FriendData) -> Result<(), FriendsGatewayError>;
+    fn remove_friend(&self, id: &FriendId) -> Result<(), FriendsGatewayError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FriendsLoadReport {
+    pub rejected_records: Vec<RejectedRecord>,
+    pub resolved_conflicts: Vec<ResolvedConflict>,
+    pub matched_date_formats: Vec<MatchedDateFormat>,
+}
+
+impl FriendsLoadReport {
+    pub fn new(rejected_records: Vec<RejectedRecord>) -> Self {
+        Self {
+            rejected_records,
+            resolved_conflicts: Vec::new(),
+            matched_date_formats: Vec::new(),
+        }
+    }
+
+    pub fn with_matched_date_formats(self, matched_date_formats: Vec<MatchedDateFormat>) -> Self {
+        Self {
+            matched_date_formats,
+            ..self
+        }
+    }
+}
+
+// The format the birthdate of the friend at the line, or at the index of a JSON array, was read in
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchedDateFormat {
+    pub line: usize,
+    pub format: DateFormat,
+}
+
+impl MatchedDateFormat {
+    pub fn new(line: usize, format: DateFormat) -> Self {
+        Self { line, format }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedConflict {
+    pub resolved: FriendData,
+    pub duplicates: Vec<FriendData>,
+}
+
+impl ResolvedConflict {
+    pub fn new(resolved: FriendData, duplicates: Vec<FriendData>) -> Self {
+        Self {
+            resolved,
+            duplicates,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RejectedRecord {
+    pub line: usize,
+    pub content: String,
+    pub reason: InvalidFriendRecord,
+}
+
+impl RejectedRecord {
+    pub fn new(line: usize, content: &str, reason: InvalidFriendRecord) -> Self {
+        Self {
+            line,
+            content: content.to_owned(),
+            reason,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FriendsGatewayError {
+    Io(String),
+    InvalidRecord {
+        line: usize,
+        error: InvalidFriendRecord,
+    },
+    InvalidJson(String),
+    InvalidEntry {
+        index: usize,
+        error: InvalidFriendRecord,
+    },
+    Database(String),
+    InvalidRow {
+        id: i64,
+        error: InvalidFriendRecord,
+    },
+    Write(String),
+    FriendNotFound(FriendId),
+}
+
+impl Display for FriendsGatewayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FriendsGatewayError::Io(message) => write!(f, "unable to read friends: {}", message),
+            FriendsGatewayError::InvalidRecord { line, error } => {
+                write!(f, "invalid friend at line {}: {}", line, error)
+            }
+            FriendsGatewayError::InvalidJson(message) => write!(f, "invalid JSON: {}", message),
+            FriendsGatewayError::InvalidEntry { index, error } => {
+                write!(f, "invalid friend at index {}: {}", index, error)
+            }
+            FriendsGatewayError::Database(message) => write!(f, "database error: {}", message),
+            FriendsGatewayError::InvalidRow { id, error } => {
+                write!(f, "invalid friend with id {}: {}", id, error)
+            }
+            FriendsGatewayError::Write(message) => {
+                write!(f, "unable to write friends: {}", message)
+            }
+            FriendsGatewayError::FriendNotFound(id) => write!(f, "friend with id {} not found", id),
+        }
+    }
+}
+
+impl std::error::Error for FriendsGatewayError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidFriendRecord {
+    MissingField(String),
+    InvalidField(String),
+    InvalidDate(String),
+    AmbiguousDate(String),
+    InvalidTimeZone(String),
+    InvalidLocale(String),
+    InvalidEncoding,
+    MalformedCsv(String),
+    MalformedVCard(String),
+    NotAnObject,
+}
+
+impl Display for InvalidFriendRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidFriendRecord::MissingField(field) => write!(f, "missing field '{}'", field),
+            InvalidFriendRecord::InvalidField(field) => write!(f, "invalid field '{}'", field),
+            InvalidFriendRecord::InvalidDate(value) => write!(f, "invalid date '{}'", value),
+            InvalidFriendRecord::AmbiguousDate(value) => write!(f, "ambiguous date '{}'", value),
+            InvalidFriendRecord::InvalidTimeZone(value) => {
+                write!(f, "invalid time zone '{}'", value)
+            }
+            InvalidFriendRecord::InvalidLocale(value) => write!(f, "invalid locale '{}'", value),
+            InvalidFriendRecord::InvalidEncoding => write!(f, "invalid UTF-8 encoding"),
+            InvalidFriendRecord::MalformedCsv(reason) => write!(f, "malformed CSV: {}", reason),
+            InvalidFriendRecord::MalformedVCard(reason) => write!(f, "malformed vCard: {}", reason),
+            InvalidFriendRecord::NotAnObject => write!(f, "not an object"),
+        }
+    }
 }
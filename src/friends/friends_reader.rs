@@ -0,0 +1,31 @@
+use std::{
+    cell::RefCell,
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    time::SystemTime,
+};
+
+// A file is rewound before each load, any other reader can only be loaded from once
+pub(crate) enum FriendsReader {
+    File(File),
+    Stream(RefCell<Box<dyn Read>>),
+}
+
+impl FriendsReader {
+    pub(crate) fn read_to_end(&self) -> io::Result<Vec<u8>> {
+        let mut content = Vec::new();
+        match self {
+            FriendsReader::File(file) => (&*file)
+                .seek(SeekFrom::Start(0))
+                .and_then(|_| (&*file).read_to_end(&mut content)),
+            FriendsReader::Stream(reader) => reader.borrow_mut().read_to_end(&mut content),
+        }
+        .map(|_| content)
+    }
+    pub(crate) fn last_modified(&self) -> Option<SystemTime> {
+        match self {
+            FriendsReader::File(file) => file.metadata().and_then(|m| m.modified()).ok(),
+            FriendsReader::Stream(_) => None,
+        }
+    }
+}
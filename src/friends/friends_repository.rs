@@ -1,6 +1,9 @@
 use std::rc::Rc;
 
-use super::{friend::Friend, friends_gateway::FriendsGateway};
+use super::{
+    friend::Friend,
+    friends_gateway::{FriendsGateway, FriendsGatewayError, FriendsLoadReport},
+};
 
 pub(crate) struct FriendsRepository {
     pub(crate) friends_gateway: Rc<dyn FriendsGateway>,
@@ -11,11 +14,16 @@ impl FriendsRepository {
         Self { friends_gateway }
     }
 
-    pub(crate) fn get_all(&self) -> Vec<Friend> {
-        self.friends_gateway
-            .get_friends()
-            .iter()
-            .map(Friend::from)
-            .collect()
+    pub(crate) fn get_born_on(
+        &self,
+        month_days: &[(u32, u32)],
+    ) -> Result<(Vec<Friend>, FriendsLoadReport), FriendsGatewayError> {
+        let (friends, report) = self.friends_gateway.get_friends_born_on(month_days)?;
+        Ok((friends.iter().map(Friend::from).collect(), report))
+    }
+
+    pub(crate) fn get_all(&self) -> Result<(Vec<Friend>, FriendsLoadReport), FriendsGatewayError> {
+        let (friends, report) = self.friends_gateway.get_friends_with_report()?;
+        Ok((friends.iter().map(Friend::from).collect(), report))
     }
 }
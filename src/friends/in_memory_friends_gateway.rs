@@ -0,0 +1,61 @@
+use std::cell::{Cell, RefCell};
+
+use super::{
+    friend_data::{FriendData, FriendId},
+    friends_gateway::{FriendsGateway, FriendsGatewayError, WritableFriendsGateway},
+};
+
+pub struct InMemoryFriendsGateway {
+    friends: RefCell<Vec<FriendData>>,
+    last_id: Cell<u64>,
+}
+
+impl InMemoryFriendsGateway {
+    pub fn new() -> Self {
+        Self {
+            friends: RefCell::new(Vec::new()),
+            last_id: Cell::new(0),
+        }
+    }
+
+    fn position(&self, id: &FriendId) -> Result<usize, FriendsGatewayError> {
+        self.friends
+            .borrow()
+            .iter()
+            .position(|friend| friend.id == *id)
+            .ok_or_else(|| FriendsGatewayError::FriendNotFound(id.clone()))
+    }
+}
+
+impl Default for InMemoryFriendsGateway {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FriendsGateway for InMemoryFriendsGateway {
+    fn get_friends(&self) -> Result<Vec<FriendData>, FriendsGatewayError> {
+        Ok(self.friends.borrow().clone())
+    }
+}
+
+impl WritableFriendsGateway for InMemoryFriendsGateway {
+    fn add_friend(&self, friend: FriendData) -> Result<FriendId, FriendsGatewayError> {
+        self.last_id.set(self.last_id.get() + 1);
+        let id = FriendId::new(&self.last_id.get().to_string());
+        self.friends.borrow_mut().push(friend.with_id(id.clone()));
+        Ok(id)
+    }
+
+    fn update_friend(&self, id: &FriendId, friend: FriendData) -> Result<(), FriendsGatewayError> {
+        let position = self.position(id)?;
+        self.friends.borrow_mut()[position] = friend.with_id(id.clone());
+        Ok(())
+    }
+
+    fn remove_friend(&self, id: &FriendId) -> Result<(), FriendsGatewayError> {
+        let position = self.position(id)?;
+        self.friends.borrow_mut().remove(position);
+        Ok(())
+    }
+}
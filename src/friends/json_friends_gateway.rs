@@ -0,0 +1,123 @@
+use std::{cell::RefCell, fs::File, io::Read, time::SystemTime};
+
+use serde_json::{Map, Value};
+
+use super::{
+    birthdate_parser::{BirthdateParser, DateFormat},
+    friend_data::{self, FriendData, FriendId},
+    friends_gateway::{
+        FriendsGateway, FriendsGatewayError, FriendsLoadReport, InvalidFriendRecord,
+        MatchedDateFormat,
+    },
+    friends_reader::FriendsReader,
+};
+
+pub struct JsonFriendsGateway {
+    reader: FriendsReader,
+    birthdate_parser: BirthdateParser,
+}
+
+impl JsonFriendsGateway {
+    pub fn new(file: File) -> Self {
+        Self::from_friends_reader(FriendsReader::File(file))
+    }
+
+    // Friends can be loaded only once from a reader, unlike from a file
+    pub fn from_reader(reader: impl Read + 'static) -> Self {
+        Self::from_friends_reader(FriendsReader::Stream(RefCell::new(Box::new(reader))))
+    }
+
+    fn from_friends_reader(reader: FriendsReader) -> Self {
+        Self {
+            reader,
+            birthdate_parser: BirthdateParser::default(),
+        }
+    }
+
+    pub fn configure_birthdate_parser(&mut self, birthdate_parser: BirthdateParser) {
+        self.birthdate_parser = birthdate_parser
+    }
+
+    fn parse_friend(
+        &self,
+        friend_data: &Value,
+    ) -> Result<(FriendData, DateFormat), InvalidFriendRecord> {
+        let friend_data = friend_data
+            .as_object()
+            .ok_or(InvalidFriendRecord::NotAnObject)?;
+        let parsed_birthdate = self
+            .birthdate_parser
+            .parse_field(string_field(friend_data, "birthdate")?)?;
+        let mut friend = FriendData::new(
+            string_field(friend_data, "name")?,
+            string_field(friend_data, "surname")?,
+            parsed_birthdate.birthdate,
+            string_field(friend_data, "email")?,
+            string_field(friend_data, "phone_number")?,
+        );
+        if friend_data.contains_key("locale") {
+            friend.locale = friend_data::parse_locale(string_field(friend_data, "locale")?)?;
+        }
+        if friend_data.contains_key("time_zone") {
+            friend.time_zone =
+                friend_data::parse_time_zone(string_field(friend_data, "time_zone")?)?;
+        }
+        let friend = match friend_data.get("id") {
+            None | Some(Value::Null) => friend,
+            Some(Value::String(id)) if !id.is_empty() => friend.with_id(FriendId::new(id)),
+            Some(Value::Number(id)) => friend.with_id(FriendId::new(&id.to_string())),
+            Some(_) => return Err(InvalidFriendRecord::InvalidField("id".to_owned())),
+        };
+        Ok((friend, parsed_birthdate.format))
+    }
+}
+
+impl FriendsGateway for JsonFriendsGateway {
+    fn get_friends(&self) -> Result<Vec<FriendData>, FriendsGatewayError> {
+        self.get_friends_with_report().map(|(friends, _)| friends)
+    }
+
+    // Matched date formats are reported by the index of the friend in the array
+    fn get_friends_with_report(
+        &self,
+    ) -> Result<(Vec<FriendData>, FriendsLoadReport), FriendsGatewayError> {
+        let content = self
+            .reader
+            .read_to_end()
+            .map_err(|e| FriendsGatewayError::Io(e.to_string()))?;
+        let friends: Value = serde_json::from_slice(&content)
+            .map_err(|e| FriendsGatewayError::InvalidJson(e.to_string()))?;
+        let friends = friends.as_array().ok_or_else(|| {
+            FriendsGatewayError::InvalidJson("expected an array of friends".to_owned())
+        })?;
+        let mut parsed_friends = Vec::new();
+        let mut matched_date_formats = Vec::new();
+        for (index, friend_data) in friends.iter().enumerate() {
+            let (friend, format) = self
+                .parse_friend(friend_data)
+                .map_err(|error| FriendsGatewayError::InvalidEntry { index, error })?;
+            parsed_friends.push(friend);
+            matched_date_formats.push(MatchedDateFormat::new(index, format));
+        }
+        Ok((
+            parsed_friends,
+            FriendsLoadReport::new(Vec::new()).with_matched_date_formats(matched_date_formats),
+        ))
+    }
+
+    fn last_modified(&self) -> Option<SystemTime> {
+        self.reader.last_modified()
+    }
+}
+
+fn string_field<'a>(
+    friend_data: &'a Map<String, Value>,
+    field: &str,
+) -> Result<&'a str, InvalidFriendRecord> {
+    match friend_data.get(field) {
+        None => Err(InvalidFriendRecord::MissingField(field.to_owned())),
+        Some(value) => value
+            .as_str()
+            .ok_or_else(|| InvalidFriendRecord::InvalidField(field.to_owned())),
+    }
+}
@@ -0,0 +1,185 @@
+use std::{
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use rusqlite::{params_from_iter, Connection, Row};
+
+use super::{
+    birthdate::Birthdate,
+    friend_data::{self, FriendData, FriendId},
+    friends_gateway::{
+        FriendsGateway, FriendsGatewayError, FriendsLoadReport, InvalidFriendRecord,
+    },
+};
+
+const MIGRATIONS: [&str; 4] = [
+    "CREATE TABLE friends (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        surname TEXT NOT NULL,
+        birth_year INTEGER,
+        birth_month INTEGER NOT NULL CHECK (birth_month BETWEEN 1 AND 12),
+        birth_day INTEGER NOT NULL CHECK (birth_day BETWEEN 1 AND 31),
+        email TEXT NOT NULL,
+        phone_number TEXT NOT NULL
+    );
+    CREATE INDEX friends_birthday ON friends (birth_month, birth_day);",
+    "ALTER TABLE friends ADD COLUMN time_zone TEXT;",
+    "ALTER TABLE friends ADD COLUMN locale TEXT;",
+    // Milliseconds since the epoch, kept up to date by triggers unless set explicitly
+    "ALTER TABLE friends ADD COLUMN modified_at INTEGER;
+    CREATE TRIGGER friends_inserted AFTER INSERT ON friends WHEN NEW.modified_at IS NULL
+    BEGIN
+        UPDATE friends SET modified_at = CAST(unixepoch('subsec') * 1000 AS INTEGER)
+        WHERE id = NEW.id;
+    END;
+    CREATE TRIGGER friends_updated AFTER UPDATE ON friends
+    WHEN NEW.modified_at IS OLD.modified_at
+    BEGIN
+        UPDATE friends SET modified_at = CAST(unixepoch('subsec') * 1000 AS INTEGER)
+        WHERE id = NEW.id;
+    END;",
+];
+
+const SELECT_FRIENDS: &str = "SELECT id, name, surname, birth_year, birth_month, birth_day, email, phone_number, time_zone, locale FROM friends";
+
+pub struct SqliteFriendsGateway {
+    connection: Connection,
+}
+
+impl SqliteFriendsGateway {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, FriendsGatewayError> {
+        Self::from_connection(Connection::open(path).map_err(database_error)?)
+    }
+
+    pub fn from_connection(connection: Connection) -> Result<Self, FriendsGatewayError> {
+        let gateway = Self { connection };
+        gateway.migrate()?;
+        Ok(gateway)
+    }
+
+    pub fn schema_version(&self) -> Result<u32, FriendsGatewayError> {
+        self.connection
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(database_error)
+    }
+
+    fn migrate(&self) -> Result<(), FriendsGatewayError> {
+        let schema_version = self.schema_version()? as usize;
+        if schema_version > MIGRATIONS.len() {
+            return Err(FriendsGatewayError::Database(format!(
+                "unsupported schema version {}",
+                schema_version
+            )));
+        }
+        for (version, migration) in MIGRATIONS.iter().enumerate().skip(schema_version) {
+            let transaction = self
+                .connection
+                .unchecked_transaction()
+                .map_err(database_error)?;
+            transaction
+                .execute_batch(migration)
+                .and_then(|_| {
+                    transaction.execute_batch(&format!("PRAGMA user_version = {}", version + 1))
+                })
+                .and_then(|_| transaction.commit())
+                .map_err(database_error)?;
+        }
+        Ok(())
+    }
+
+    fn query_friends(
+        &self,
+        sql: &str,
+        params: Vec<u32>,
+    ) -> Result<Vec<FriendData>, FriendsGatewayError> {
+        let mut statement = self.connection.prepare(sql).map_err(database_error)?;
+        let rows = statement
+            .query_map(params_from_iter(params), read_friend)
+            .map_err(database_error)?;
+        rows.map(|row| row.map_err(database_error)?).collect()
+    }
+}
+
+impl FriendsGateway for SqliteFriendsGateway {
+    fn get_friends(&self) -> Result<Vec<FriendData>, FriendsGatewayError> {
+        self.query_friends(&format!("{} ORDER BY id", SELECT_FRIENDS), Vec::new())
+    }
+
+    fn get_friends_born_on(
+        &self,
+        month_days: &[(u32, u32)],
+    ) -> Result<(Vec<FriendData>, FriendsLoadReport), FriendsGatewayError> {
+        if month_days.is_empty() {
+            return Ok((Vec::new(), FriendsLoadReport::default()));
+        }
+        let conditions = vec!["(birth_month = ? AND birth_day = ?)"; month_days.len()];
+        let sql = format!(
+            "{} WHERE {} ORDER BY id",
+            SELECT_FRIENDS,
+            conditions.join(" OR ")
+        );
+        let params = month_days
+            .iter()
+            .flat_map(|(month, day)| [*month, *day])
+            .collect();
+        Ok((
+            self.query_friends(&sql, params)?,
+            FriendsLoadReport::default(),
+        ))
+    }
+
+    fn last_modified(&self) -> Option<SystemTime> {
+        let modified_at: Option<i64> = self
+            .connection
+            .query_row("SELECT MAX(modified_at) FROM friends", [], |row| row.get(0))
+            .ok()?;
+        Some(UNIX_EPOCH + Duration::from_millis(u64::try_from(modified_at?).ok()?))
+    }
+}
+
+fn read_friend(row: &Row) -> rusqlite::Result<Result<FriendData, FriendsGatewayError>> {
+    let id: i64 = row.get("id")?;
+    let birth_year: Option<i32> = row.get("birth_year")?;
+    let birth_month: u32 = row.get("birth_month")?;
+    let birth_day: u32 = row.get("birth_day")?;
+    let birthdate = match birth_year {
+        Some(year) => Birthdate::from_ymd_opt(year, birth_month, birth_day),
+        None => Birthdate::from_md_opt(birth_month, birth_day),
+    };
+    let Some(birthdate) = birthdate else {
+        return Ok(Err(FriendsGatewayError::InvalidRow {
+            id,
+            error: InvalidFriendRecord::InvalidDate(match birth_year {
+                Some(year) => format!("{}-{:02}-{:02}", year, birth_month, birth_day),
+                None => format!("--{:02}-{:02}", birth_month, birth_day),
+            }),
+        }));
+    };
+    let time_zone: Option<String> = row.get("time_zone")?;
+    let time_zone = match friend_data::parse_time_zone(&time_zone.unwrap_or_default()) {
+        Ok(time_zone) => time_zone,
+        Err(error) => return Ok(Err(FriendsGatewayError::InvalidRow { id, error })),
+    };
+    let locale: Option<String> = row.get("locale")?;
+    let locale = match friend_data::parse_locale(&locale.unwrap_or_default()) {
+        Ok(locale) => locale,
+        Err(error) => return Ok(Err(FriendsGatewayError::InvalidRow { id, error })),
+    };
+    let mut friend = FriendData::new(
+        &row.get::<_, String>("name")?,
+        &row.get::<_, String>("surname")?,
+        birthdate,
+        &row.get::<_, String>("email")?,
+        &row.get::<_, String>("phone_number")?,
+    )
+    .with_id(FriendId::new(&id.to_string()));
+    friend.time_zone = time_zone;
+    friend.locale = locale;
+    Ok(Ok(friend))
+}
+
+fn database_error(error: rusqlite::Error) -> FriendsGatewayError {
+    FriendsGatewayError::Database(error.to_string())
+}
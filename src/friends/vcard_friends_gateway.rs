@@ -0,0 +1,324 @@
+use std::{cell::RefCell, fs::File, io::Read, time::SystemTime};
+
+use super::{
+    birthdate_parser::{BirthdateParser, DateFormat, ParsedBirthdate},
+    friend_data::{self, FriendData, FriendId},
+    friends_gateway::{
+        FriendsGateway, FriendsGatewayError, FriendsLoadReport, InvalidFriendRecord,
+        MatchedDateFormat, RejectedRecord,
+    },
+    friends_reader::FriendsReader,
+};
+
+pub struct VCardFriendsGateway {
+    reader: FriendsReader,
+    birthdate_parser: BirthdateParser,
+}
+
+impl VCardFriendsGateway {
+    pub fn new(file: File) -> Self {
+        Self::from_friends_reader(FriendsReader::File(file))
+    }
+
+    // Friends can be loaded only once from a reader, unlike from a file
+    pub fn from_reader(reader: impl Read + 'static) -> Self {
+        Self::from_friends_reader(FriendsReader::Stream(RefCell::new(Box::new(reader))))
+    }
+
+    fn from_friends_reader(reader: FriendsReader) -> Self {
+        Self {
+            reader,
+            birthdate_parser: BirthdateParser::new(vec![
+                DateFormat::iso(),
+                DateFormat::new("%Y%m%d"),
+                DateFormat::iso_month_day(),
+                DateFormat::new("--%m%d"),
+            ]),
+        }
+    }
+
+    pub fn configure_birthdate_parser(&mut self, birthdate_parser: BirthdateParser) {
+        self.birthdate_parser = birthdate_parser
+    }
+
+    fn parse_friend(
+        &self,
+        contact: &VCard,
+        bday: &Property,
+        email: Option<&Property>,
+        tel: Option<&Property>,
+    ) -> Result<(FriendData, DateFormat), FriendsGatewayError> {
+        let parsed_birthdate = self.parse_birthdate(bday)?;
+        let (name, surname) = contact
+            .name()
+            .ok_or_else(|| FriendsGatewayError::InvalidRecord {
+                line: contact.line,
+                error: InvalidFriendRecord::MissingField("N".to_owned()),
+            })?;
+        let email = email
+            .map(|email| unescape(&email.value))
+            .unwrap_or_default();
+        let phone_number = tel.map(|tel| unescape(&tel.value)).unwrap_or_default();
+        let phone_number = phone_number.strip_prefix("tel:").unwrap_or(&phone_number);
+        let mut friend = FriendData::new(
+            &name,
+            &surname,
+            parsed_birthdate.birthdate,
+            &email,
+            phone_number,
+        );
+        if let Some(tz) = contact.property("TZ") {
+            // Only IANA names are supported, a bare UTC offset does not follow daylight saving time
+            friend.time_zone =
+                friend_data::parse_time_zone(&unescape(&tz.value)).map_err(|error| {
+                    FriendsGatewayError::InvalidRecord {
+                        line: tz.line,
+                        error,
+                    }
+                })?;
+        }
+        if let Some(lang) = contact.property("LANG") {
+            friend.locale = friend_data::parse_locale(&unescape(&lang.value)).map_err(|error| {
+                FriendsGatewayError::InvalidRecord {
+                    line: lang.line,
+                    error,
+                }
+            })?;
+        }
+        let friend = match contact.property("UID").map(|p| unescape(&p.value)) {
+            Some(uid) if !uid.is_empty() => friend.with_id(FriendId::new(&uid)),
+            _ => friend,
+        };
+        Ok((friend, parsed_birthdate.format))
+    }
+
+    // A date-time birthday like "19531015T231000Z" is read by its date
+    fn parse_birthdate(&self, bday: &Property) -> Result<ParsedBirthdate, FriendsGatewayError> {
+        let date = bday
+            .value
+            .split_once('T')
+            .map_or(bday.value.as_str(), |(date, _)| date);
+        self.birthdate_parser.parse_field(date).map_err(|error| {
+            FriendsGatewayError::InvalidRecord {
+                line: bday.line,
+                error,
+            }
+        })
+    }
+}
+
+impl FriendsGateway for VCardFriendsGateway {
+    fn get_friends(&self) -> Result<Vec<FriendData>, FriendsGatewayError> {
+        self.get_friends_with_report().map(|(friends, _)| friends)
+    }
+
+    fn get_friends_with_report(
+        &self,
+    ) -> Result<(Vec<FriendData>, FriendsLoadReport), FriendsGatewayError> {
+        let content = self
+            .reader
+            .read_to_end()
+            .map_err(|e| FriendsGatewayError::Io(e.to_string()))?;
+        let content = String::from_utf8(content).map_err(|e| {
+            let valid_content = &e.as_bytes()[..e.utf8_error().valid_up_to()];
+            FriendsGatewayError::InvalidRecord {
+                line: valid_content.iter().filter(|b| **b == b'\n').count() + 1,
+                error: InvalidFriendRecord::InvalidEncoding,
+            }
+        })?;
+
+        let mut friends = Vec::new();
+        let mut rejected_records = Vec::new();
+        let mut matched_date_formats = Vec::new();
+        for contact in read_vcards(&content)? {
+            // Contacts without a birthday cannot be greeted, address books are full of them
+            let Some(bday) = contact.property("BDAY") else {
+                continue;
+            };
+            // Nor can those with neither an email address nor a phone number, but they are worth reporting
+            let (email, tel) = (contact.property("EMAIL"), contact.property("TEL"));
+            if email.is_none() && tel.is_none() {
+                rejected_records.push(RejectedRecord::new(
+                    contact.line,
+                    &contact.content,
+                    InvalidFriendRecord::MissingField("EMAIL or TEL".to_owned()),
+                ));
+                continue;
+            }
+            let (friend, format) = self.parse_friend(&contact, bday, email, tel)?;
+            friends.push(friend);
+            // Matched date formats are reported by the line of the birthday
+            matched_date_formats.push(MatchedDateFormat::new(bday.line, format));
+        }
+        Ok((
+            friends,
+            FriendsLoadReport::new(rejected_records)
+                .with_matched_date_formats(matched_date_formats),
+        ))
+    }
+
+    fn last_modified(&self) -> Option<SystemTime> {
+        self.reader.last_modified()
+    }
+}
+
+struct Property {
+    line: usize,
+    name: String,
+    value: String,
+}
+
+struct VCard {
+    line: usize,
+    content: String,
+    properties: Vec<Property>,
+}
+
+impl VCard {
+    fn property(&self, name: &str) -> Option<&Property> {
+        self.properties.iter().find(|p| p.name == name)
+    }
+
+    fn name(&self) -> Option<(String, String)> {
+        let structured_name = self.property("N").map(|n| split_components(&n.value));
+        if let Some([family_name, given_name, ..]) = structured_name.as_deref() {
+            if !family_name.is_empty() || !given_name.is_empty() {
+                return Some((given_name.to_owned(), family_name.to_owned()));
+            }
+        }
+        let formatted_name = unescape(&self.property("FN")?.value);
+        match formatted_name.trim().rsplit_once(' ') {
+            Some((given_name, family_name)) => {
+                Some((given_name.trim().to_owned(), family_name.to_owned()))
+            }
+            None if !formatted_name.trim().is_empty() => {
+                Some((formatted_name.trim().to_owned(), String::new()))
+            }
+            None => None,
+        }
+    }
+}
+
+fn read_vcards(content: &str) -> Result<Vec<VCard>, FriendsGatewayError> {
+    let mut vcards = Vec::new();
+    let mut current: Option<VCard> = None;
+    for (line, content_line) in unfold(content) {
+        if content_line.trim().is_empty() {
+            continue;
+        }
+        let property = parse_property(line, &content_line)?;
+        match (property.name.as_str(), current.as_mut()) {
+            ("BEGIN", None) if property.value.eq_ignore_ascii_case("VCARD") => {
+                current = Some(VCard {
+                    line,
+                    content: content_line,
+                    properties: Vec::new(),
+                })
+            }
+            ("END", Some(vcard)) if property.value.eq_ignore_ascii_case("VCARD") => {
+                vcard.content.push('\n');
+                vcard.content.push_str(&content_line);
+                vcards.extend(current.take())
+            }
+            (_, Some(vcard)) => {
+                vcard.content.push('\n');
+                vcard.content.push_str(&content_line);
+                vcard.properties.push(property)
+            }
+            (_, None) => {
+                return Err(FriendsGatewayError::InvalidRecord {
+                    line,
+                    error: InvalidFriendRecord::MalformedVCard(
+                        "property outside of BEGIN:VCARD".to_owned(),
+                    ),
+                })
+            }
+        }
+    }
+    match current {
+        Some(vcard) => Err(FriendsGatewayError::InvalidRecord {
+            line: vcard.line,
+            error: InvalidFriendRecord::MalformedVCard("missing END:VCARD".to_owned()),
+        }),
+        None => Ok(vcards),
+    }
+}
+
+fn unfold(content: &str) -> Vec<(usize, String)> {
+    let mut content_lines: Vec<(usize, String)> = Vec::new();
+    for (index, physical_line) in content.lines().enumerate() {
+        match (
+            physical_line.strip_prefix([' ', '\t']),
+            content_lines.last_mut(),
+        ) {
+            (Some(continuation), Some((_, content_line))) => content_line.push_str(continuation),
+            _ => content_lines.push((index + 1, physical_line.to_owned())),
+        }
+    }
+    content_lines
+}
+
+fn parse_property(line: usize, content_line: &str) -> Result<Property, FriendsGatewayError> {
+    let mut in_quotes = false;
+    let separator = content_line.char_indices().find(|(_, c)| match c {
+        '"' => {
+            in_quotes = !in_quotes;
+            false
+        }
+        ':' => !in_quotes,
+        _ => false,
+    });
+    let Some((separator, _)) = separator else {
+        return Err(FriendsGatewayError::InvalidRecord {
+            line,
+            error: InvalidFriendRecord::MalformedVCard(format!(
+                "missing ':' in '{}'",
+                content_line
+            )),
+        });
+    };
+    let name = content_line[..separator]
+        .split(';')
+        .next()
+        .unwrap_or_default();
+    let name = name.rsplit('.').next().unwrap_or_default();
+    Ok(Property {
+        line,
+        name: name.trim().to_uppercase(),
+        value: content_line[separator + 1..].trim().to_owned(),
+    })
+}
+
+fn split_components(value: &str) -> Vec<String> {
+    let mut components = vec![String::new()];
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        let component = components.last_mut().unwrap();
+        match c {
+            '\\' => {
+                component.push(c);
+                component.extend(chars.next());
+            }
+            ';' => components.push(String::new()),
+            c => component.push(c),
+        }
+    }
+    components.iter().map(|c| unescape(c.trim())).collect()
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(escaped) => unescaped.push(escaped),
+            None => {}
+        }
+    }
+    unescaped
+}
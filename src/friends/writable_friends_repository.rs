@@ -0,0 +1,36 @@
+use std::rc::Rc;
+
+use super::{
+    friend_data::{FriendData, FriendId},
+    friends_gateway::{FriendsGatewayError, WritableFriendsGateway},
+};
+
+pub struct WritableFriendsRepository {
+    friends_gateway: Rc<dyn WritableFriendsGateway>,
+}
+
+impl WritableFriendsRepository {
+    pub fn new(friends_gateway: Rc<impl WritableFriendsGateway + 'static>) -> Self {
+        Self { friends_gateway }
+    }
+
+    pub fn get_all(&self) -> Result<Vec<FriendData>, FriendsGatewayError> {
+        self.friends_gateway.get_friends()
+    }
+
+    pub fn find(&self, id: &FriendId) -> Result<Option<FriendData>, FriendsGatewayError> {
+        Ok(self.get_all()?.into_iter().find(|friend| friend.id == *id))
+    }
+
+    pub fn add(&self, friend: FriendData) -> Result<FriendId, FriendsGatewayError> {
+        self.friends_gateway.add_friend(friend)
+    }
+
+    pub fn update(&self, id: &FriendId, friend: FriendData) -> Result<(), FriendsGatewayError> {
+        self.friends_gateway.update_friend(id, friend)
+    }
+
+    pub fn remove(&self, id: &FriendId) -> Result<(), FriendsGatewayError> {
+        self.friends_gateway.remove_friend(id)
+    }
+}
@@ -1,30 +1,130 @@
-use chrono::NaiveDate;
-use std::rc::Rc;
+use chrono::{DateTime, Days, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::{fmt::Display, rc::Rc};
+
+pub use crate::friends::birthdate::LeapDayPolicy;
 
 use crate::{
     friends::{
-        friend::Friend, friend_data::FriendData, friends_gateway::FriendsGateway,
+        friend::Friend,
+        friend_data::{FriendData, FriendId},
+        friends_gateway::{FriendsGateway, FriendsGatewayError, FriendsLoadReport},
         friends_repository::FriendsRepository,
     },
-    greetings::{greeting::Greeting, greetings_sender::GreetingsSender},
+    greetings::{
+        greeting::{Greeting, GreetingKind, Milestone, Reminder},
+        greetings_sender::{GreetingsSender, SendGreetingsError},
+    },
+    last_runs::last_run_store::{LastRunStore, LastRunStoreError},
 };
 
 pub trait Calendar {
     fn today(&self) -> NaiveDate;
+
+    // A calendar only knowing the date is taken to be at the very start of it
+    fn now(&self) -> DateTime<Utc> {
+        let start_of_today = self.today().and_time(NaiveTime::MIN);
+        self.time_zone()
+            .from_local_datetime(&start_of_today)
+            .earliest()
+            .map(|now| now.with_timezone(&Utc))
+            .unwrap_or_else(|| start_of_today.and_utc())
+    }
+
+    fn time_zone(&self) -> Tz {
+        Tz::UTC
+    }
 }
 
 pub trait Observer {
     fn observe_friends_celebrating_their_birthdays(&self, _friends: Vec<FriendData>) {}
+    fn observe_friends_load_report(&self, _report: FriendsLoadReport) {}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReminderRecipient {
+    Person {
+        name: String,
+        surname: String,
+        email: String,
+        phone_number: String,
+    },
+    OtherFriends,
+}
+
+impl ReminderRecipient {
+    pub fn person(name: &str, surname: &str, email: &str, phone_number: &str) -> Self {
+        ReminderRecipient::Person {
+            name: name.to_owned(),
+            surname: surname.to_owned(),
+            email: email.to_owned(),
+            phone_number: phone_number.to_owned(),
+        }
+    }
+}
+
+pub(crate) struct Reminders {
+    pub(crate) days_before: u32,
+    pub(crate) recipient: ReminderRecipient,
 }
 
 pub struct DummyObserver {}
 impl Observer for DummyObserver {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum GreeterServiceError {
+    FriendsNotLoaded(FriendsGatewayError),
+    GreetingsNotSent(SendGreetingsError),
+    LastRunNotTracked(LastRunStoreError),
+}
+
+impl From<FriendsGatewayError> for GreeterServiceError {
+    fn from(error: FriendsGatewayError) -> Self {
+        GreeterServiceError::FriendsNotLoaded(error)
+    }
+}
+
+impl From<SendGreetingsError> for GreeterServiceError {
+    fn from(error: SendGreetingsError) -> Self {
+        GreeterServiceError::GreetingsNotSent(error)
+    }
+}
+
+impl From<LastRunStoreError> for GreeterServiceError {
+    fn from(error: LastRunStoreError) -> Self {
+        GreeterServiceError::LastRunNotTracked(error)
+    }
+}
+
+impl Display for GreeterServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GreeterServiceError::FriendsNotLoaded(error) => error.fmt(f),
+            GreeterServiceError::GreetingsNotSent(error) => write!(
+                f,
+                "unable to send {} greetings",
+                error.greetings_not_sent.len()
+            ),
+            GreeterServiceError::LastRunNotTracked(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for GreeterServiceError {}
+
 pub struct GreeterService {
     pub(crate) friends_repository: FriendsRepository,
     pub(crate) calendar: Rc<dyn Calendar>,
     pub(crate) greetings_sender: Rc<dyn GreetingsSender>,
     pub(crate) observer: Rc<dyn Observer>,
+    pub(crate) leap_day_policy: LeapDayPolicy,
+    pub(crate) run_interval: Option<Duration>,
+    pub(crate) last_run_store: Option<Rc<dyn LastRunStore>>,
+    pub(crate) belated_greetings: bool,
+    pub(crate) reminders: Option<Reminders>,
+    pub(crate) birthday_announcements: Option<Reminders>,
+    pub(crate) milestones: Vec<u32>,
+    pub(crate) milestone_recipient: Option<ReminderRecipient>,
 }
 
 impl GreeterService {
@@ -38,76 +138,487 @@ impl GreeterService {
             calendar,
             greetings_sender,
             observer: Rc::new(DummyObserver {}),
+            leap_day_policy: LeapDayPolicy::February28,
+            run_interval: None,
+            last_run_store: None,
+            belated_greetings: false,
+            reminders: None,
+            birthday_announcements: None,
+            milestones: Vec::new(),
+            milestone_recipient: None,
         }
     }
 
-    pub fn run(&self) {
-        let friends_celebrating_birthdays = self.get_friends_celebrating_birthday();
-        self.send_greetings(friends_celebrating_birthdays);
+    pub fn run(&self) -> Result<(), GreeterServiceError> {
+        let today = self.calendar.today();
+        let now = self.calendar.now();
+        let last_run = match &self.last_run_store {
+            Some(last_run_store) => last_run_store.last_run()?,
+            None => None,
+        };
+        let previous_run = self.previous_run(last_run, now);
+        let reminded_birthdays: Vec<(&Reminders, NaiveDate)> = self
+            .reminders
+            .iter()
+            .chain(&self.birthday_announcements)
+            .filter_map(|reminders| {
+                self.reminded_birthday(today, previous_run, reminders)
+                    .map(|birthday| (reminders, birthday))
+            })
+            .collect();
+        let friends = self.load_friends(today, last_run, &reminded_birthdays)?;
+        let friends_celebrating_birthdays =
+            self.get_friends_celebrating_birthday(&friends, today, last_run, previous_run);
+        let mut greetings = self.to_greetings(friends_celebrating_birthdays);
+        if let Some(milestone_recipient) = &self.milestone_recipient {
+            greetings.extend(self.get_milestone_notices(&friends, &greetings, milestone_recipient));
+        }
+        for (reminders, birthday) in &reminded_birthdays {
+            greetings.extend(self.get_reminders(&friends, *birthday, reminders));
+        }
+        let sent_greetings = match &self.last_run_store {
+            Some(last_run_store) => last_run_store.sent_greetings()?,
+            None => Vec::new(),
+        };
+        greetings.retain(|g| !sent_greetings.contains(&Self::sent_greeting_key(g)));
+        if let Err(error) = self.greetings_sender.send(greetings.clone()) {
+            // The run isn't recorded, so the next one retries it, but only for the greetings not sent
+            if let Some(last_run_store) = &self.last_run_store {
+                let newly_sent_greetings = greetings
+                    .iter()
+                    .filter(|g| error.greetings_not_sent.iter().all(|(ng, _)| ng != *g))
+                    .map(Self::sent_greeting_key);
+                let sent_greetings: Vec<String> = sent_greetings
+                    .into_iter()
+                    .chain(newly_sent_greetings)
+                    .collect();
+                last_run_store.record_sent_greetings(&sent_greetings)?;
+            }
+            return Err(error.into());
+        }
+        if let Some(last_run_store) = &self.last_run_store {
+            // Never move the last run backwards, or missed dates would be processed twice
+            if last_run.is_none_or(|last_run| last_run < now) {
+                last_run_store.record_run(now)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Identifies a greeting whichever day it is sent on, a birthday greeting being belated or not
+    fn sent_greeting_key(greeting: &Greeting) -> String {
+        let kind = match &greeting.kind {
+            GreetingKind::Birthday | GreetingKind::BelatedBirthday => "birthday".to_owned(),
+            GreetingKind::Reminder(reminder) => format!("reminder {}", reminder.birthday),
+            GreetingKind::Milestone(milestone) => format!(
+                "milestone {} {} {}",
+                milestone.age, milestone.friend_name, milestone.friend_surname
+            ),
+        };
+        format!("{} to {}", kind, greeting.friend_id.as_str())
+            .escape_debug()
+            .to_string()
+    }
+
+    // Running more than once a day, the run before tells whose local day started since.
+    // With catch-up it is the last run that succeeded, so an outage or a failure greets no one less
+    fn previous_run(
+        &self,
+        last_run: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let run_interval = self.run_interval?;
+        match self.last_run_store {
+            Some(_) => last_run,
+            None => Some(now - run_interval),
+        }
+    }
+
+    // The local dates of the friend after the one of the last run and before the current one
+    fn missed_dates(
+        &self,
+        friend: &Friend,
+        local_date: NaiveDate,
+        last_run: Option<DateTime<Utc>>,
+    ) -> Vec<NaiveDate> {
+        let Some(last_run) = last_run else {
+            return Vec::new();
+        };
+        let last_run_date = last_run
+            .with_timezone(&self.calendar.time_zone())
+            .date_naive();
+        self.local_date(friend, last_run_date, last_run)
+            .iter_days()
+            .skip(1)
+            .take_while(|date| *date < local_date)
+            .collect()
     }
 
-    fn get_friends_celebrating_birthday(&self) -> Vec<Friend> {
-        let celebreting_friends: Vec<Friend> = self
-            .friends_repository
-            .get_all()
+    // Friends are loaded once per run, as gateways reading a stream can't read it twice
+    fn load_friends(
+        &self,
+        today: NaiveDate,
+        last_run: Option<DateTime<Utc>>,
+        reminded_birthdays: &[(&Reminders, NaiveDate)],
+    ) -> Result<Vec<Friend>, FriendsGatewayError> {
+        let (friends, load_report) = if self.needs_all_friends(reminded_birthdays) {
+            self.friends_repository.get_all()?
+        } else {
+            // Around the world the local date can be a day behind or ahead of ours
+            let last_run_date = last_run.map(|last_run| {
+                last_run
+                    .with_timezone(&self.calendar.time_zone())
+                    .date_naive()
+            });
+            let missed_dates = last_run_date
+                .into_iter()
+                .flat_map(|date| date.iter_days().take_while(|date| *date < today));
+            let mut month_days = Vec::new();
+            for date in [today.pred_opt(), Some(today), today.succ_opt()]
+                .into_iter()
+                .flatten()
+                .chain(missed_dates)
+                .chain(reminded_birthdays.iter().map(|(_, birthday)| *birthday))
+            {
+                for month_day in Friend::birthdays_celebrated_on(date, self.leap_day_policy) {
+                    if !month_days.contains(&month_day) {
+                        month_days.push(month_day);
+                    }
+                }
+            }
+            self.friends_repository.get_born_on(&month_days)?
+        };
+        self.observer.observe_friends_load_report(load_report);
+        Ok(friends)
+    }
+
+    // Reminders and milestone notices addressed to the other friends need all of them
+    fn needs_all_friends(&self, reminded_birthdays: &[(&Reminders, NaiveDate)]) -> bool {
+        let reminds_other_friends = reminded_birthdays
+            .iter()
+            .any(|(reminders, _)| reminders.recipient == ReminderRecipient::OtherFriends);
+        reminds_other_friends || self.milestone_recipient == Some(ReminderRecipient::OtherFriends)
+    }
+
+    fn get_friends_celebrating_birthday(
+        &self,
+        friends: &[Friend],
+        today: NaiveDate,
+        last_run: Option<DateTime<Utc>>,
+        previous_run: Option<DateTime<Utc>>,
+    ) -> Vec<(Friend, GreetingKind, NaiveDate)> {
+        let now = self.calendar.now();
+        let mut celebreting_friends: Vec<(Friend, GreetingKind, NaiveDate)> = friends
             .iter()
-            .filter(|f| f.is_it_their_birthday(self.calendar.today()))
-            .cloned()
+            .filter(|f| self.is_it_their_birthday_now(f, today, now, previous_run))
+            .map(|f| {
+                let local_date = self.local_date(f, today, now);
+                (f.clone(), GreetingKind::Birthday, local_date)
+            })
             .collect();
 
-        let celebreting_friends_data = celebreting_friends.iter().map(Friend::to).collect();
+        let missed_kind = match self.belated_greetings {
+            true => GreetingKind::BelatedBirthday,
+            false => GreetingKind::Birthday,
+        };
+        // Each friend is greeted once, for the most recent birthday they missed
+        let mut missed_friends: Vec<(Friend, GreetingKind, NaiveDate)> = Vec::new();
+        for friend in friends {
+            if celebreting_friends
+                .iter()
+                .any(|(f, _, _)| f.id == friend.id)
+            {
+                continue;
+            }
+            let local_date = self.local_date(friend, today, now);
+            let missed_birthday = self
+                .missed_dates(friend, local_date, last_run)
+                .into_iter()
+                .rev()
+                .find(|date| friend.is_it_their_birthday(*date, self.leap_day_policy));
+            if let Some(date) = missed_birthday {
+                missed_friends.push((friend.clone(), missed_kind.clone(), date));
+            }
+        }
+        missed_friends.sort_by_key(|(_, _, date)| *date);
+        celebreting_friends.extend(missed_friends);
+
+        let celebreting_friends_data = celebreting_friends
+            .iter()
+            .map(|(f, _, _)| Friend::to(f))
+            .collect();
         self.observer
             .observe_friends_celebrating_their_birthdays(celebreting_friends_data);
 
         celebreting_friends
     }
 
-    fn send_greetings(&self, friends: Vec<Friend>) {
-        let greetings: Vec<Greeting> = friends
+    fn is_it_their_birthday_now(
+        &self,
+        friend: &Friend,
+        today: NaiveDate,
+        now: DateTime<Utc>,
+        previous_run: Option<DateTime<Utc>>,
+    ) -> bool {
+        let local_date = self.local_date(friend, today, now);
+        // Running more than once a day, a friend is greeted only by the first run since their local midnight
+        friend.is_it_their_birthday(local_date, self.leap_day_policy)
+            && previous_run.is_none_or(|previous_run| {
+                friend.local_date(previous_run, self.calendar.time_zone()) != local_date
+            })
+    }
+
+    fn local_date(&self, friend: &Friend, today: NaiveDate, now: DateTime<Utc>) -> NaiveDate {
+        match (self.run_interval, friend.time_zone) {
+            (None, None) => today,
+            _ => friend.local_date(now, self.calendar.time_zone()),
+        }
+    }
+
+    fn to_greetings(&self, friends: Vec<(Friend, GreetingKind, NaiveDate)>) -> Vec<Greeting> {
+        friends
+            .into_iter()
+            .map(|(f, kind, date)| {
+                let greeting = Greeting::new(&f.id, &f.name, &f.surname, &f.email, &f.phone_number)
+                    .with_kind(kind)
+                    .with_birthdate(f.birthdate)
+                    .with_locale(f.locale.clone());
+                match f.age_on(date) {
+                    Some(age) => greeting
+                        .with_age(age)
+                        .with_milestone(self.milestones.contains(&age)),
+                    None => greeting,
+                }
+            })
+            .collect()
+    }
+
+    fn get_milestone_notices(
+        &self,
+        friends: &[Friend],
+        greetings: &[Greeting],
+        recipient: &ReminderRecipient,
+    ) -> Vec<Greeting> {
+        let milestone_greetings: Vec<&Greeting> =
+            greetings.iter().filter(|g| g.milestone).collect();
+        if milestone_greetings.is_empty() {
+            return Vec::new();
+        }
+        let recipients = Self::get_recipients(friends, recipient);
+        let mut notices = Vec::new();
+        for greeting in milestone_greetings {
+            let Some(age) = greeting.age else {
+                continue;
+            };
+            let milestone = Milestone::new(&greeting.friend_name, &greeting.friend_surname, age);
+            for recipient in recipients
+                .iter()
+                .filter(|r| r.friend_id != greeting.friend_id)
+            {
+                notices.push(
+                    recipient
+                        .clone()
+                        .with_kind(GreetingKind::Milestone(milestone.clone())),
+                );
+            }
+        }
+        notices
+    }
+
+    fn get_recipients(friends: &[Friend], recipient: &ReminderRecipient) -> Vec<Greeting> {
+        match recipient {
+            ReminderRecipient::Person {
+                name,
+                surname,
+                email,
+                phone_number,
+            } => vec![Greeting::new(
+                &FriendId::new(email),
+                name,
+                surname,
+                email,
+                phone_number,
+            )],
+            ReminderRecipient::OtherFriends => friends
+                .iter()
+                .map(|f| {
+                    Greeting::new(&f.id, &f.name, &f.surname, &f.email, &f.phone_number)
+                        .with_locale(f.locale.clone())
+                })
+                .collect(),
+        }
+    }
+
+    // Reminders go out once, on the day exactly the configured number of days before the birthday
+    fn reminded_birthday(
+        &self,
+        today: NaiveDate,
+        previous_run: Option<DateTime<Utc>>,
+        reminders: &Reminders,
+    ) -> Option<NaiveDate> {
+        let previous_run_date =
+            previous_run.map(|run| run.with_timezone(&self.calendar.time_zone()).date_naive());
+        if previous_run_date == Some(today) {
+            return None;
+        }
+        today.checked_add_days(Days::new(reminders.days_before.into()))
+    }
+
+    fn get_reminders(
+        &self,
+        friends: &[Friend],
+        birthday: NaiveDate,
+        reminders: &Reminders,
+    ) -> Vec<Greeting> {
+        let upcoming_friends: Vec<&Friend> = friends
             .iter()
-            .map(|f| Greeting::new(&f.name, &f.surname, &f.email, &f.phone_number))
+            .filter(|f| f.is_it_their_birthday(birthday, self.leap_day_policy))
             .collect();
+        if upcoming_friends.is_empty() {
+            return Vec::new();
+        }
 
-        // This should be handled properly...
-        let _ = self.greetings_sender.send(greetings);
+        // Friends celebrating together are reminded only of each other
+        let mut greetings = Vec::new();
+        for recipient in Self::get_recipients(friends, &reminders.recipient) {
+            let reminder = upcoming_friends
+                .iter()
+                .filter(|f| f.id != recipient.friend_id)
+                .fold(
+                    Reminder::new(birthday, reminders.days_before),
+                    |reminder, f| reminder.with_friend(&f.name, &f.surname),
+                );
+            if !reminder.friends.is_empty() {
+                greetings.push(recipient.with_kind(GreetingKind::Reminder(reminder)));
+            }
+        }
+        greetings
     }
 
     pub fn configure_observer(&mut self, observer: Rc<impl Observer + 'static>) {
         self.observer = observer
     }
+
+    pub fn configure_leap_day_policy(&mut self, leap_day_policy: LeapDayPolicy) {
+        self.leap_day_policy = leap_day_policy
+    }
+
+    pub fn configure_run_interval(&mut self, run_interval: Duration) {
+        self.run_interval = Some(run_interval)
+    }
+
+    pub fn configure_catch_up(&mut self, last_run_store: Rc<impl LastRunStore + 'static>) {
+        self.last_run_store = Some(last_run_store)
+    }
+
+    pub fn configure_belated_greetings(&mut self, belated_greetings: bool) {
+        self.belated_greetings = belated_greetings
+    }
+
+    pub fn configure_reminders(&mut self, days_before: u32, recipient: ReminderRecipient) {
+        self.reminders = Some(Reminders {
+            days_before,
+            recipient,
+        })
+    }
+
+    // On each birthday, the other friends are told of it, alongside any advance reminder
+    pub fn configure_birthday_announcements(&mut self) {
+        self.birthday_announcements = Some(Reminders {
+            days_before: 0,
+            recipient: ReminderRecipient::OtherFriends,
+        })
+    }
+
+    pub fn configure_milestones(&mut self, milestones: Vec<u32>) {
+        self.milestones = milestones
+    }
+
+    pub fn configure_milestone_recipient(&mut self, recipient: ReminderRecipient) {
+        self.milestone_recipient = Some(recipient)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::friends::friend_data::FriendData;
+    use crate::calendars::fixed_calendar::FixedCalendar;
+    use crate::friends::json_friends_gateway::JsonFriendsGateway;
+    use crate::friends::{
+        birthdate::Birthdate,
+        friend_data::{FriendData, FriendId, Locale},
+        friends_gateway::{InvalidFriendRecord, RejectedRecord},
+    };
+    use crate::greetings::greetings_sender::SendSingleGreetingError;
+    use crate::last_runs::in_memory_last_run_store::InMemoryLastRunStore;
     use chrono::{NaiveDate, Utc};
     use std::cell::RefCell;
+    use std::io::Cursor;
 
     struct FriendsGatewayTestDouble {
-        stubbed_friends: RefCell<Vec<FriendData>>,
+        stubbed_friends: RefCell<Result<Vec<FriendData>, FriendsGatewayError>>,
+        stubbed_load_report: RefCell<FriendsLoadReport>,
+        requested_month_days: RefCell<Vec<(u32, u32)>>,
     }
 
     impl FriendsGatewayTestDouble {
         fn new() -> Self {
             Self {
-                stubbed_friends: RefCell::new(Vec::new()),
+                stubbed_friends: RefCell::new(Ok(Vec::new())),
+                stubbed_load_report: RefCell::new(FriendsLoadReport::default()),
+                requested_month_days: RefCell::new(Vec::new()),
             }
         }
 
+        fn spied_requested_month_days(&self) -> Vec<(u32, u32)> {
+            self.requested_month_days.borrow().clone()
+        }
+
+        fn stub_load_report(&self, report: FriendsLoadReport) {
+            let _ = self.stubbed_load_report.replace(report);
+        }
+
         fn stub_friends(&self, friends: Vec<FriendData>) {
-            self.stubbed_friends.replace(friends);
+            let _ = self.stubbed_friends.replace(Ok(friends));
         }
 
         fn stub_no_friends(&self) {
-            self.stubbed_friends.replace(Vec::new());
+            let _ = self.stubbed_friends.replace(Ok(Vec::new()));
+        }
+
+        fn stub_error(&self, error: FriendsGatewayError) {
+            let _ = self.stubbed_friends.replace(Err(error));
         }
     }
 
     impl FriendsGateway for FriendsGatewayTestDouble {
-        fn get_friends(&self) -> Vec<FriendData> {
+        fn get_friends(&self) -> Result<Vec<FriendData>, FriendsGatewayError> {
             self.stubbed_friends.borrow().clone()
         }
+
+        fn get_friends_with_report(
+            &self,
+        ) -> Result<(Vec<FriendData>, FriendsLoadReport), FriendsGatewayError> {
+            Ok((
+                self.get_friends()?,
+                self.stubbed_load_report.borrow().clone(),
+            ))
+        }
+
+        fn get_friends_born_on(
+            &self,
+            month_days: &[(u32, u32)],
+        ) -> Result<(Vec<FriendData>, FriendsLoadReport), FriendsGatewayError> {
+            self.requested_month_days
+                .borrow_mut()
+                .extend_from_slice(month_days);
+            let (friends, report) = self.get_friends_with_report()?;
+            let friends = friends
+                .into_iter()
+                .filter(|f| month_days.contains(&(f.birthdate.month(), f.birthdate.day())))
+                .collect();
+            Ok((friends, report))
+        }
     }
 
     struct CalendarTestDouble {
@@ -160,24 +671,34 @@ mod tests {
 
     struct ObserverTestDouble {
         observed_friends: RefCell<Vec<FriendData>>,
+        observed_load_reports: RefCell<Vec<FriendsLoadReport>>,
     }
 
     impl ObserverTestDouble {
         fn new() -> Self {
             Self {
                 observed_friends: RefCell::new(Vec::new()),
+                observed_load_reports: RefCell::new(Vec::new()),
             }
         }
 
         fn spied_observed_friends(&self) -> Vec<FriendData> {
             self.observed_friends.borrow().clone()
         }
+
+        fn spied_observed_load_reports(&self) -> Vec<FriendsLoadReport> {
+            self.observed_load_reports.borrow().clone()
+        }
     }
 
     impl Observer for ObserverTestDouble {
         fn observe_friends_celebrating_their_birthdays(&self, friends: Vec<FriendData>) {
             self.observed_friends.borrow_mut().extend(friends)
         }
+
+        fn observe_friends_load_report(&self, report: FriendsLoadReport) {
+            self.observed_load_reports.borrow_mut().push(report)
+        }
     }
 
     #[test]
@@ -208,14 +729,30 @@ mod tests {
             calendar,
             Rc::clone(&greetings_sender),
         );
-        greeter.run();
+        greeter.run().unwrap();
 
         let sent_greetings = greetings_sender.spied_sent_greetings();
         assert_eq!(
             sent_greetings,
             vec![
-                Greeting::new("Mario", "Franco", "mario-franco@email.com", "3331112224"),
-                Greeting::new("Carla", "Sandri", "carla-sandri@email.com", "3335556667")
+                Greeting::new(
+                    &FriendId::new("franco-mario-19700824-mario-franco@email.com"),
+                    "Mario",
+                    "Franco",
+                    "mario-franco@email.com",
+                    "3331112224"
+                )
+                .with_birthdate(NaiveDate::from_ymd_opt(1970, 8, 24).unwrap().into())
+                .with_age(53),
+                Greeting::new(
+                    &FriendId::new("sandri-carla-19800824-carla-sandri@email.com"),
+                    "Carla",
+                    "Sandri",
+                    "carla-sandri@email.com",
+                    "3335556667"
+                )
+                .with_birthdate(NaiveDate::from_ymd_opt(1980, 8, 24).unwrap().into())
+                .with_age(43)
             ]
         )
     }
@@ -248,7 +785,7 @@ mod tests {
             calendar,
             Rc::clone(&greetings_sender),
         );
-        greeter.run();
+        greeter.run().unwrap();
 
         let sent_greetings = greetings_sender.spied_sent_greetings();
         assert_eq!(sent_greetings, Vec::new())
@@ -282,17 +819,20 @@ mod tests {
             calendar,
             Rc::clone(&greetings_sender),
         );
-        greeter.run();
+        greeter.run().unwrap();
 
         let sent_greetings = greetings_sender.spied_sent_greetings();
         assert_eq!(
             sent_greetings,
             vec![Greeting::new(
+                &FriendId::new("sandri-carla-19800612-carla-sandri@email.com"),
                 "Carla",
                 "Sandri",
                 "carla-sandri@email.com",
                 "3335556667"
-            )]
+            )
+            .with_birthdate(NaiveDate::from_ymd_opt(1980, 6, 12).unwrap().into())
+            .with_age(43)]
         )
     }
 
@@ -307,7 +847,7 @@ mod tests {
             Rc::new(CalendarTestDouble::new()),
             Rc::clone(&greetings_sender),
         );
-        greeter.run();
+        greeter.run().unwrap();
 
         let sent_greetings = greetings_sender.spied_sent_greetings();
         assert_eq!(sent_greetings, Vec::new())
@@ -342,18 +882,97 @@ mod tests {
             calendar,
             Rc::clone(&greetings_sender),
         );
-        greeter.run();
+        greeter.run().unwrap();
 
         let sent_greetings = greetings_sender.spied_sent_greetings();
         assert_eq!(
             sent_greetings,
             vec![
-                Greeting::new("Mario", "Franco", "mario-franco@email.com", "3331112224"),
-                Greeting::new("Carla", "Sandri", "carla-sandri@email.com", "3335556667")
+                Greeting::new(
+                    &FriendId::new("franco-mario-19990228-mario-franco@email.com"),
+                    "Mario",
+                    "Franco",
+                    "mario-franco@email.com",
+                    "3331112224"
+                )
+                .with_birthdate(NaiveDate::from_ymd_opt(1999, 2, 28).unwrap().into())
+                .with_age(24),
+                Greeting::new(
+                    &FriendId::new("sandri-carla-20000229-carla-sandri@email.com"),
+                    "Carla",
+                    "Sandri",
+                    "carla-sandri@email.com",
+                    "3335556667"
+                )
+                .with_birthdate(NaiveDate::from_ymd_opt(2000, 2, 29).unwrap().into())
+                .with_age(23)
             ]
         )
     }
 
+    #[test]
+    fn send_greetings_to_friends_with_unknown_birth_year_who_celebrate_their_birthday_today() {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_friends(vec![
+            FriendData::new(
+                "Mario",
+                "Franco",
+                Birthdate::from_md_opt(8, 24).unwrap(),
+                "mario-franco@email.com",
+                "3331112224",
+            ),
+            FriendData::new(
+                "Carla",
+                "Sandri",
+                Birthdate::from_md_opt(8, 25).unwrap(),
+                "carla-sandri@email.com",
+                "3335556667",
+            ),
+        ]);
+        let calendar = Rc::new(CalendarTestDouble::new());
+        calendar.stub_today(NaiveDate::from_ymd_opt(2023, 8, 24).unwrap());
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+
+        let greeter = GreeterService::new(
+            Rc::clone(&friends_gateway),
+            calendar,
+            Rc::clone(&greetings_sender),
+        );
+        greeter.run().unwrap();
+
+        let sent_greetings = greetings_sender.spied_sent_greetings();
+        assert_eq!(
+            sent_greetings,
+            vec![Greeting::new(
+                &FriendId::new("franco-mario-0824-mario-franco@email.com"),
+                "Mario",
+                "Franco",
+                "mario-franco@email.com",
+                "3331112224"
+            )
+            .with_birthdate(Birthdate::from_md_opt(8, 24).unwrap())]
+        )
+    }
+
+    #[test]
+    fn ask_the_friends_gateway_only_for_friends_born_on_the_days_celebrated_around_today() {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        let calendar = Rc::new(CalendarTestDouble::new());
+        calendar.stub_today(NaiveDate::from_ymd_opt(2023, 2, 28).unwrap());
+
+        let greeter = GreeterService::new(
+            Rc::clone(&friends_gateway),
+            calendar,
+            Rc::new(GreetingsSenderTestDouble::new()),
+        );
+        greeter.run().unwrap();
+
+        assert_eq!(
+            friends_gateway.spied_requested_month_days(),
+            vec![(2, 27), (2, 28), (2, 29), (3, 1)]
+        )
+    }
+
     #[test]
     fn observe_friends_celebrating_their_birthdays() {
         let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
@@ -384,7 +1003,7 @@ mod tests {
         );
         greeter.configure_observer(Rc::clone(&observer));
 
-        greeter.run();
+        greeter.run().unwrap();
 
         let notified_friends = observer.spied_observed_friends();
         assert_eq!(
@@ -398,4 +1017,965 @@ mod tests {
             )]
         )
     }
+
+    #[test]
+    fn surface_friends_loading_errors_without_sending_any_greeting() {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        let loading_error = FriendsGatewayError::InvalidRecord {
+            line: 3,
+            error: InvalidFriendRecord::InvalidDate("31/02/1980".to_string()),
+        };
+        friends_gateway.stub_error(loading_error.clone());
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+
+        let greeter = GreeterService::new(
+            Rc::clone(&friends_gateway),
+            Rc::new(CalendarTestDouble::new()),
+            Rc::clone(&greetings_sender),
+        );
+        let run_result = greeter.run();
+
+        assert_eq!(
+            run_result,
+            Err(GreeterServiceError::FriendsNotLoaded(loading_error))
+        );
+        assert_eq!(greetings_sender.spied_sent_greetings(), Vec::new())
+    }
+
+    #[test]
+    fn observe_the_friends_load_report() {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_no_friends();
+        let load_report = FriendsLoadReport::new(vec![RejectedRecord::new(
+            2,
+            "Franco, Mario, 31/02/1970, mario-franco@email.com, 3331112224",
+            InvalidFriendRecord::InvalidDate("31/02/1970".to_string()),
+        )]);
+        friends_gateway.stub_load_report(load_report.clone());
+        let observer = Rc::new(ObserverTestDouble::new());
+        let mut greeter = GreeterService::new(
+            Rc::clone(&friends_gateway),
+            Rc::new(CalendarTestDouble::new()),
+            Rc::new(GreetingsSenderTestDouble::new()),
+        );
+        greeter.configure_observer(Rc::clone(&observer));
+
+        greeter.run().unwrap();
+
+        assert_eq!(observer.spied_observed_load_reports(), vec![load_report])
+    }
+
+    #[test]
+    fn greet_and_observe_friends_by_their_id() {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        let carla = FriendData::new(
+            "Carla",
+            "Sandri",
+            NaiveDate::from_ymd_opt(1980, 6, 12).unwrap(),
+            "carla-sandri@email.com",
+            "3335556667",
+        )
+        .with_id(FriendId::new("42"));
+        friends_gateway.stub_friends(vec![carla.clone()]);
+        let calendar = Rc::new(CalendarTestDouble::new());
+        calendar.stub_today(NaiveDate::from_ymd_opt(2023, 6, 12).unwrap());
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let observer = Rc::new(ObserverTestDouble::new());
+        let mut greeter = GreeterService::new(
+            Rc::clone(&friends_gateway),
+            calendar,
+            Rc::clone(&greetings_sender),
+        );
+        greeter.configure_observer(Rc::clone(&observer));
+
+        greeter.run().unwrap();
+
+        assert_eq!(
+            greetings_sender
+                .spied_sent_greetings()
+                .iter()
+                .map(|g| g.friend_id.clone())
+                .collect::<Vec<_>>(),
+            vec![FriendId::new("42")]
+        );
+        assert_eq!(observer.spied_observed_friends(), vec![carla])
+    }
+
+    fn greets_leap_day_friend_on(date: NaiveDate, leap_day_policy: LeapDayPolicy) -> bool {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_friends(vec![FriendData::new(
+            "Carla",
+            "Sandri",
+            NaiveDate::from_ymd_opt(1996, 2, 29).unwrap(),
+            "carla-sandri@email.com",
+            "3335556667",
+        )]);
+        let calendar = Rc::new(CalendarTestDouble::new());
+        calendar.stub_today(date);
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let mut greeter = GreeterService::new(
+            Rc::clone(&friends_gateway),
+            calendar,
+            Rc::clone(&greetings_sender),
+        );
+        greeter.configure_leap_day_policy(leap_day_policy);
+
+        greeter.run().unwrap();
+
+        !greetings_sender.spied_sent_greetings().is_empty()
+    }
+
+    #[test]
+    fn during_leap_years_greet_friends_born_on_feb_29th_only_once() {
+        for year in [2000, 2024, 2028] {
+            for leap_day_policy in [
+                LeapDayPolicy::February28,
+                LeapDayPolicy::March1,
+                LeapDayPolicy::Skip,
+            ] {
+                let greetings: Vec<bool> = [(2, 28), (2, 29), (3, 1)]
+                    .into_iter()
+                    .map(|(month, day)| {
+                        let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+                        greets_leap_day_friend_on(date, leap_day_policy)
+                    })
+                    .collect();
+
+                assert_eq!(
+                    greetings,
+                    vec![false, true, false],
+                    "{} {:?}",
+                    year,
+                    leap_day_policy
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn during_not_leap_years_greet_friends_born_on_feb_29th_on_feb_28th_by_default() {
+        for year in [1900, 2023, 2025, 2100] {
+            let feb_28th = NaiveDate::from_ymd_opt(year, 2, 28).unwrap();
+            let mar_1st = NaiveDate::from_ymd_opt(year, 3, 1).unwrap();
+
+            assert!(greets_leap_day_friend_on(
+                feb_28th,
+                LeapDayPolicy::February28
+            ));
+            assert!(!greets_leap_day_friend_on(
+                mar_1st,
+                LeapDayPolicy::February28
+            ));
+        }
+    }
+
+    #[test]
+    fn during_not_leap_years_greet_friends_born_on_feb_29th_on_mar_1st_when_configured() {
+        for year in [1900, 2023, 2025, 2100] {
+            let feb_28th = NaiveDate::from_ymd_opt(year, 2, 28).unwrap();
+            let mar_1st = NaiveDate::from_ymd_opt(year, 3, 1).unwrap();
+
+            assert!(!greets_leap_day_friend_on(feb_28th, LeapDayPolicy::March1));
+            assert!(greets_leap_day_friend_on(mar_1st, LeapDayPolicy::March1));
+        }
+    }
+
+    #[test]
+    fn during_not_leap_years_skip_friends_born_on_feb_29th_when_configured() {
+        for year in [1900, 2023, 2025, 2100] {
+            let feb_28th = NaiveDate::from_ymd_opt(year, 2, 28).unwrap();
+            let mar_1st = NaiveDate::from_ymd_opt(year, 3, 1).unwrap();
+
+            assert!(!greets_leap_day_friend_on(feb_28th, LeapDayPolicy::Skip));
+            assert!(!greets_leap_day_friend_on(mar_1st, LeapDayPolicy::Skip));
+        }
+    }
+
+    fn friend_living_in(name: &str, month: u32, day: u32, time_zone: Option<Tz>) -> FriendData {
+        let friend = FriendData::new(
+            name,
+            "Sandri",
+            NaiveDate::from_ymd_opt(1980, month, day).unwrap(),
+            "carla-sandri@email.com",
+            "3335556667",
+        );
+        match time_zone {
+            Some(time_zone) => friend.with_time_zone(time_zone),
+            None => friend,
+        }
+    }
+
+    #[test]
+    fn greet_friends_on_their_local_birthday() {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_friends(vec![
+            friend_living_in("Aiko", 8, 25, Some(Tz::Asia__Tokyo)),
+            friend_living_in("Yuki", 8, 24, Some(Tz::Asia__Tokyo)),
+            friend_living_in("John", 8, 24, Some(Tz::America__New_York)),
+            friend_living_in("Carla", 8, 24, None),
+        ]);
+        let calendar = Rc::new(FixedCalendar::at(
+            Tz::UTC.with_ymd_and_hms(2023, 8, 24, 20, 0, 0).unwrap(),
+        ));
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let greeter = GreeterService::new(
+            Rc::clone(&friends_gateway),
+            calendar,
+            Rc::clone(&greetings_sender),
+        );
+
+        greeter.run().unwrap();
+
+        assert_eq!(
+            greetings_sender
+                .spied_sent_greetings()
+                .iter()
+                .map(|g| g.friend_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Aiko", "John", "Carla"]
+        );
+    }
+
+    #[test]
+    fn when_running_hourly_greet_each_friend_once_as_their_local_day_starts() {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_friends(vec![
+            friend_living_in("Aiko", 8, 25, Some(Tz::Asia__Tokyo)),
+            friend_living_in("John", 8, 25, Some(Tz::America__New_York)),
+            friend_living_in("Carla", 8, 25, None),
+        ]);
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let first_run = Tz::Europe__Rome
+            .with_ymd_and_hms(2023, 8, 23, 0, 0, 0)
+            .unwrap();
+
+        for hour in 0..96 {
+            let calendar = Rc::new(FixedCalendar::at(first_run + Duration::hours(hour)));
+            let mut greeter = GreeterService::new(
+                Rc::clone(&friends_gateway),
+                calendar,
+                Rc::clone(&greetings_sender),
+            );
+            greeter.configure_run_interval(Duration::hours(1));
+            greeter.run().unwrap();
+        }
+
+        assert_eq!(
+            greetings_sender
+                .spied_sent_greetings()
+                .iter()
+                .map(|g| g.friend_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Aiko", "Carla", "John"]
+        );
+    }
+
+    fn start_of(date: NaiveDate) -> DateTime<Utc> {
+        date.and_time(NaiveTime::MIN).and_utc()
+    }
+
+    fn catch_up_greeter(
+        today: NaiveDate,
+        last_run_store: Rc<InMemoryLastRunStore>,
+        greetings_sender: Rc<GreetingsSenderTestDouble>,
+    ) -> GreeterService {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_friends(vec![
+            friend_living_in("Aiko", 8, 21, None),
+            friend_living_in("John", 8, 22, None),
+            friend_living_in("Carla", 8, 24, None),
+        ]);
+        let mut greeter = GreeterService::new(
+            friends_gateway,
+            Rc::new(FixedCalendar::new(today)),
+            greetings_sender,
+        );
+        greeter.configure_catch_up(last_run_store);
+        greeter
+    }
+
+    #[test]
+    fn catch_up_on_birthdays_missed_since_the_last_run() {
+        let last_run_store = Rc::new(InMemoryLastRunStore::new());
+        last_run_store
+            .record_run(start_of(NaiveDate::from_ymd_opt(2023, 8, 21).unwrap()))
+            .unwrap();
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let today = NaiveDate::from_ymd_opt(2023, 8, 24).unwrap();
+        let greeter = catch_up_greeter(
+            today,
+            Rc::clone(&last_run_store),
+            Rc::clone(&greetings_sender),
+        );
+
+        greeter.run().unwrap();
+
+        assert_eq!(
+            greetings_sender
+                .spied_sent_greetings()
+                .iter()
+                .map(|g| (g.friend_name.as_str(), g.kind.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Carla", GreetingKind::Birthday),
+                ("John", GreetingKind::Birthday)
+            ]
+        );
+        assert_eq!(last_run_store.last_run(), Ok(Some(start_of(today))));
+    }
+
+    #[test]
+    fn catch_up_on_the_birthdays_of_homonyms_born_on_the_same_day() {
+        let friends_gateway = Rc::new(JsonFriendsGateway::from_reader(Cursor::new(
+            r#"[
+                {"name": "Mario", "surname": "Rossi", "birthdate": "22/08/1980",
+                 "email": "mario@rossi.com", "phone_number": "3396665559"},
+                {"name": "Mario", "surname": "Rossi", "birthdate": "22/08/1980",
+                 "email": "mario.rossi@email.com", "phone_number": "3396665550"}
+            ]"#,
+        )));
+        let last_run_store = Rc::new(InMemoryLastRunStore::new());
+        last_run_store
+            .record_run(start_of(NaiveDate::from_ymd_opt(2023, 8, 21).unwrap()))
+            .unwrap();
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let mut greeter = GreeterService::new(
+            friends_gateway,
+            Rc::new(FixedCalendar::new(
+                NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(),
+            )),
+            Rc::clone(&greetings_sender),
+        );
+        greeter.configure_catch_up(Rc::clone(&last_run_store));
+
+        greeter.run().unwrap();
+
+        assert_eq!(
+            greetings_sender
+                .spied_sent_greetings()
+                .iter()
+                .map(|g| g.email.as_str())
+                .collect::<Vec<_>>(),
+            vec!["mario@rossi.com", "mario.rossi@email.com"]
+        );
+    }
+
+    #[test]
+    fn send_belated_greetings_for_missed_birthdays_when_configured() {
+        let last_run_store = Rc::new(InMemoryLastRunStore::new());
+        last_run_store
+            .record_run(start_of(NaiveDate::from_ymd_opt(2023, 8, 20).unwrap()))
+            .unwrap();
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let mut greeter = catch_up_greeter(
+            NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(),
+            last_run_store,
+            Rc::clone(&greetings_sender),
+        );
+        greeter.configure_belated_greetings(true);
+
+        greeter.run().unwrap();
+
+        assert_eq!(
+            greetings_sender
+                .spied_sent_greetings()
+                .iter()
+                .map(|g| (g.friend_name.as_str(), g.kind.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Carla", GreetingKind::Birthday),
+                ("Aiko", GreetingKind::BelatedBirthday),
+                ("John", GreetingKind::BelatedBirthday)
+            ]
+        );
+    }
+
+    #[test]
+    fn catch_up_only_on_today_on_the_first_run_or_when_already_run_today() {
+        let last_run_store = Rc::new(InMemoryLastRunStore::new());
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let today = NaiveDate::from_ymd_opt(2023, 8, 24).unwrap();
+        let greeter = catch_up_greeter(
+            today,
+            Rc::clone(&last_run_store),
+            Rc::clone(&greetings_sender),
+        );
+
+        greeter.run().unwrap();
+        greeter.run().unwrap();
+
+        assert_eq!(
+            greetings_sender
+                .spied_sent_greetings()
+                .iter()
+                .map(|g| g.friend_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Carla", "Carla"]
+        );
+        assert_eq!(last_run_store.last_run(), Ok(Some(start_of(today))));
+    }
+
+    struct FailingGreetingsSender {}
+
+    impl GreetingsSender for FailingGreetingsSender {
+        fn send(&self, greetings: Vec<Greeting>) -> Result<(), SendGreetingsError> {
+            Err(SendGreetingsError::new(
+                greetings
+                    .into_iter()
+                    .map(|g| (g, SendSingleGreetingError::new("mailbox full".to_owned())))
+                    .collect(),
+            ))
+        }
+    }
+
+    #[test]
+    fn do_not_record_the_run_when_greetings_are_not_sent() {
+        let last_run = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+        let last_run_store = Rc::new(InMemoryLastRunStore::new());
+        last_run_store.record_run(start_of(last_run)).unwrap();
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_friends(vec![friend_living_in("Carla", 8, 24, None)]);
+        let mut greeter = GreeterService::new(
+            friends_gateway,
+            Rc::new(FixedCalendar::new(
+                NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(),
+            )),
+            Rc::new(FailingGreetingsSender {}),
+        );
+        greeter.configure_catch_up(Rc::clone(&last_run_store));
+
+        let run_result = greeter.run();
+
+        assert!(matches!(
+            run_result,
+            Err(GreeterServiceError::GreetingsNotSent(_))
+        ));
+        assert_eq!(last_run_store.last_run(), Ok(Some(start_of(last_run))));
+    }
+
+    struct PartiallyFailingGreetingsSender {
+        failing_friend_name: RefCell<Option<String>>,
+        sent_greetings: RefCell<Vec<Greeting>>,
+    }
+
+    impl GreetingsSender for PartiallyFailingGreetingsSender {
+        fn send(&self, greetings: Vec<Greeting>) -> Result<(), SendGreetingsError> {
+            let failing_friend_name = self.failing_friend_name.borrow().clone();
+            let (failed, sent): (Vec<Greeting>, Vec<Greeting>) = greetings
+                .into_iter()
+                .partition(|g| Some(&g.friend_name) == failing_friend_name.as_ref());
+            self.sent_greetings.borrow_mut().extend(sent);
+            if failed.is_empty() {
+                return Ok(());
+            }
+            Err(SendGreetingsError::new(
+                failed
+                    .into_iter()
+                    .map(|g| (g, SendSingleGreetingError::new("mailbox full".to_owned())))
+                    .collect(),
+            ))
+        }
+    }
+
+    #[test]
+    fn retry_only_the_greetings_not_sent_by_a_failed_run() {
+        let last_run_store = Rc::new(InMemoryLastRunStore::new());
+        last_run_store
+            .record_run(start_of(NaiveDate::from_ymd_opt(2023, 8, 23).unwrap()))
+            .unwrap();
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_friends(vec![
+            friend_living_in("Aiko", 8, 24, None),
+            friend_living_in("Carla", 8, 24, None),
+            friend_living_in("John", 8, 25, None),
+        ]);
+        let greetings_sender = Rc::new(PartiallyFailingGreetingsSender {
+            failing_friend_name: RefCell::new(Some("Carla".to_owned())),
+            sent_greetings: RefCell::new(Vec::new()),
+        });
+        let calendar = Rc::new(CalendarTestDouble::new());
+        calendar.stub_today(NaiveDate::from_ymd_opt(2023, 8, 24).unwrap());
+        let mut greeter = GreeterService::new(
+            friends_gateway,
+            Rc::clone(&calendar),
+            Rc::clone(&greetings_sender),
+        );
+        greeter.configure_catch_up(Rc::clone(&last_run_store));
+
+        assert!(greeter.run().is_err());
+        greetings_sender.failing_friend_name.replace(None);
+        calendar.stub_today(NaiveDate::from_ymd_opt(2023, 8, 25).unwrap());
+        greeter.run().unwrap();
+
+        assert_eq!(
+            greetings_sender
+                .sent_greetings
+                .borrow()
+                .iter()
+                .map(|g| g.friend_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Aiko", "John", "Carla"]
+        );
+        assert_eq!(
+            last_run_store.last_run(),
+            Ok(Some(start_of(
+                NaiveDate::from_ymd_opt(2023, 8, 25).unwrap()
+            )))
+        );
+        assert_eq!(last_run_store.sent_greetings(), Ok(Vec::new()));
+    }
+
+    fn run_hourly_at(
+        now: DateTime<Tz>,
+        friends_gateway: &Rc<FriendsGatewayTestDouble>,
+        last_run_store: &Rc<InMemoryLastRunStore>,
+        greetings_sender: &Rc<PartiallyFailingGreetingsSender>,
+    ) -> Result<(), GreeterServiceError> {
+        let mut greeter = GreeterService::new(
+            Rc::clone(friends_gateway),
+            Rc::new(FixedCalendar::at(now)),
+            Rc::clone(greetings_sender),
+        );
+        greeter.configure_run_interval(Duration::hours(1));
+        greeter.configure_catch_up(Rc::clone(last_run_store));
+        greeter.run()
+    }
+
+    #[test]
+    fn when_running_hourly_greet_friends_whose_local_day_started_while_the_service_was_down() {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_friends(vec![
+            friend_living_in("Carla", 8, 24, None),
+            friend_living_in("John", 8, 23, None),
+        ]);
+        let last_run_store = Rc::new(InMemoryLastRunStore::new());
+        last_run_store
+            .record_run(Utc.with_ymd_and_hms(2023, 8, 23, 23, 0, 0).unwrap())
+            .unwrap();
+        let greetings_sender = Rc::new(PartiallyFailingGreetingsSender {
+            failing_friend_name: RefCell::new(None),
+            sent_greetings: RefCell::new(Vec::new()),
+        });
+
+        for hour in 3..5 {
+            run_hourly_at(
+                Tz::UTC.with_ymd_and_hms(2023, 8, 24, hour, 0, 0).unwrap(),
+                &friends_gateway,
+                &last_run_store,
+                &greetings_sender,
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            greetings_sender
+                .sent_greetings
+                .borrow()
+                .iter()
+                .map(|g| g.friend_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Carla"]
+        );
+    }
+
+    #[test]
+    fn when_running_hourly_retry_the_greetings_not_sent_at_the_next_run() {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_friends(vec![
+            friend_living_in("Aiko", 8, 24, None),
+            friend_living_in("Carla", 8, 24, None),
+        ]);
+        let last_run_store = Rc::new(InMemoryLastRunStore::new());
+        last_run_store
+            .record_run(Utc.with_ymd_and_hms(2023, 8, 23, 23, 0, 0).unwrap())
+            .unwrap();
+        let greetings_sender = Rc::new(PartiallyFailingGreetingsSender {
+            failing_friend_name: RefCell::new(Some("Carla".to_owned())),
+            sent_greetings: RefCell::new(Vec::new()),
+        });
+        let run_at = |hour| {
+            run_hourly_at(
+                Tz::UTC.with_ymd_and_hms(2023, 8, 24, hour, 0, 0).unwrap(),
+                &friends_gateway,
+                &last_run_store,
+                &greetings_sender,
+            )
+        };
+
+        assert!(run_at(0).is_err());
+        greetings_sender.failing_friend_name.replace(None);
+        run_at(1).unwrap();
+        run_at(2).unwrap();
+
+        assert_eq!(
+            greetings_sender
+                .sent_greetings
+                .borrow()
+                .iter()
+                .map(|g| g.friend_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Aiko", "Carla"]
+        );
+    }
+
+    fn reminder_greeter(
+        today: NaiveDate,
+        recipient: ReminderRecipient,
+        greetings_sender: Rc<GreetingsSenderTestDouble>,
+    ) -> GreeterService {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_friends(vec![
+            friend_living_in("Aiko", 8, 27, None),
+            friend_living_in("John", 8, 28, None),
+            friend_living_in("Carla", 8, 24, None),
+        ]);
+        let mut greeter = GreeterService::new(
+            friends_gateway,
+            Rc::new(FixedCalendar::new(today)),
+            greetings_sender,
+        );
+        greeter.configure_reminders(3, recipient);
+        greeter
+    }
+
+    #[test]
+    fn remind_a_person_of_birthdays_coming_in_the_configured_number_of_days() {
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let greeter = reminder_greeter(
+            NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(),
+            ReminderRecipient::person("Anna", "Bianchi", "anna@bianchi.com", "3330001112"),
+            Rc::clone(&greetings_sender),
+        );
+
+        greeter.run().unwrap();
+
+        assert_eq!(
+            greetings_sender.spied_sent_greetings(),
+            vec![
+                Greeting::new(
+                    &FriendId::new("sandri-carla-19800824-carla-sandri@email.com"),
+                    "Carla",
+                    "Sandri",
+                    "carla-sandri@email.com",
+                    "3335556667"
+                )
+                .with_birthdate(NaiveDate::from_ymd_opt(1980, 8, 24).unwrap().into())
+                .with_age(43),
+                Greeting::new(
+                    &FriendId::new("anna@bianchi.com"),
+                    "Anna",
+                    "Bianchi",
+                    "anna@bianchi.com",
+                    "3330001112"
+                )
+                .with_kind(GreetingKind::Reminder(
+                    Reminder::new(NaiveDate::from_ymd_opt(2023, 8, 27).unwrap(), 3)
+                        .with_friend("Aiko", "Sandri")
+                ))
+            ]
+        );
+    }
+
+    #[test]
+    fn remind_the_other_friends_of_birthdays_coming_in_the_configured_number_of_days() {
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let greeter = reminder_greeter(
+            NaiveDate::from_ymd_opt(2023, 8, 25).unwrap(),
+            ReminderRecipient::OtherFriends,
+            Rc::clone(&greetings_sender),
+        );
+
+        greeter.run().unwrap();
+
+        assert_eq!(
+            greetings_sender
+                .spied_sent_greetings()
+                .iter()
+                .map(|g| (g.friend_name.as_str(), g.kind.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "Aiko",
+                    GreetingKind::Reminder(
+                        Reminder::new(NaiveDate::from_ymd_opt(2023, 8, 28).unwrap(), 3)
+                            .with_friend("John", "Sandri")
+                    )
+                ),
+                (
+                    "Carla",
+                    GreetingKind::Reminder(
+                        Reminder::new(NaiveDate::from_ymd_opt(2023, 8, 28).unwrap(), 3)
+                            .with_friend("John", "Sandri")
+                    )
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn when_running_hourly_remind_only_once_a_day() {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_friends(vec![friend_living_in("Aiko", 8, 27, None)]);
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let first_run = Tz::UTC.with_ymd_and_hms(2023, 8, 23, 0, 0, 0).unwrap();
+
+        for hour in 0..72 {
+            let calendar = Rc::new(FixedCalendar::at(first_run + Duration::hours(hour)));
+            let mut greeter = GreeterService::new(
+                Rc::clone(&friends_gateway),
+                calendar,
+                Rc::clone(&greetings_sender),
+            );
+            greeter.configure_run_interval(Duration::hours(1));
+            greeter.configure_reminders(
+                3,
+                ReminderRecipient::person("Anna", "Bianchi", "anna@bianchi.com", "3330001112"),
+            );
+            greeter.run().unwrap();
+        }
+
+        assert_eq!(greetings_sender.spied_sent_greetings().len(), 1);
+    }
+
+    #[test]
+    fn remind_the_other_friends_once_of_all_the_birthdays_they_share_a_date_with() {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_friends(vec![
+            friend_living_in("Aiko", 8, 24, None),
+            friend_living_in("John", 8, 22, None),
+            friend_living_in("Carla", 8, 24, None),
+        ]);
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let mut greeter = GreeterService::new(
+            friends_gateway,
+            Rc::new(FixedCalendar::new(
+                NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(),
+            )),
+            Rc::clone(&greetings_sender),
+        );
+        greeter.configure_birthday_announcements();
+
+        greeter.run().unwrap();
+
+        let today = NaiveDate::from_ymd_opt(2023, 8, 24).unwrap();
+        assert_eq!(
+            greetings_sender.spied_sent_greetings()[2..]
+                .iter()
+                .map(|g| (g.friend_name.as_str(), g.kind.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "Aiko",
+                    GreetingKind::Reminder(Reminder::new(today, 0).with_friend("Carla", "Sandri"))
+                ),
+                (
+                    "John",
+                    GreetingKind::Reminder(
+                        Reminder::new(today, 0)
+                            .with_friend("Aiko", "Sandri")
+                            .with_friend("Carla", "Sandri")
+                    )
+                ),
+                (
+                    "Carla",
+                    GreetingKind::Reminder(Reminder::new(today, 0).with_friend("Aiko", "Sandri"))
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn load_friends_read_from_a_stream_once_for_greetings_and_reminders() {
+        let friends_gateway = Rc::new(JsonFriendsGateway::from_reader(Cursor::new(
+            r#"[
+                {"name": "Carla", "surname": "Sandri", "birthdate": "24/08/1980",
+                 "email": "carla@sandri.com", "phone_number": "3335556667"},
+                {"name": "John", "surname": "Doe", "birthdate": "11/12/1982",
+                 "email": "john@doe.com", "phone_number": "3335556668"}
+            ]"#,
+        )));
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let mut greeter = GreeterService::new(
+            friends_gateway,
+            Rc::new(FixedCalendar::new(
+                NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(),
+            )),
+            Rc::clone(&greetings_sender),
+        );
+        greeter.configure_birthday_announcements();
+
+        greeter.run().unwrap();
+
+        let sent_greetings = greetings_sender.spied_sent_greetings();
+        assert_eq!(
+            sent_greetings
+                .iter()
+                .map(|g| (g.friend_name.as_str(), g.kind.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Carla", GreetingKind::Birthday),
+                (
+                    "John",
+                    GreetingKind::Reminder(
+                        Reminder::new(NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(), 0)
+                            .with_friend("Carla", "Sandri")
+                    )
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn announce_birthdays_to_the_other_friends_alongside_advance_reminders() {
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let mut greeter = reminder_greeter(
+            NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(),
+            ReminderRecipient::person("Anna", "Bianchi", "anna@bianchi.com", "3330001112"),
+            Rc::clone(&greetings_sender),
+        );
+        greeter.configure_birthday_announcements();
+
+        greeter.run().unwrap();
+
+        let today = NaiveDate::from_ymd_opt(2023, 8, 24).unwrap();
+        assert_eq!(
+            greetings_sender.spied_sent_greetings()[1..]
+                .iter()
+                .map(|g| (g.friend_name.as_str(), g.kind.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "Anna",
+                    GreetingKind::Reminder(
+                        Reminder::new(NaiveDate::from_ymd_opt(2023, 8, 27).unwrap(), 3)
+                            .with_friend("Aiko", "Sandri")
+                    )
+                ),
+                (
+                    "Aiko",
+                    GreetingKind::Reminder(Reminder::new(today, 0).with_friend("Carla", "Sandri"))
+                ),
+                (
+                    "John",
+                    GreetingKind::Reminder(Reminder::new(today, 0).with_friend("Carla", "Sandri"))
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn greet_friends_with_their_age_marking_milestone_ones() {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_friends(vec![
+            friend_living_in("Aiko", 8, 24, None),
+            FriendData::new(
+                "John",
+                "Sandri",
+                NaiveDate::from_ymd_opt(1983, 8, 24).unwrap(),
+                "john-sandri@email.com",
+                "3335556668",
+            ),
+            FriendData::new(
+                "Carla",
+                "Sandri",
+                Birthdate::from_md_opt(8, 24).unwrap(),
+                "carla-sandri@email.com",
+                "3335556667",
+            ),
+        ]);
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let mut greeter = GreeterService::new(
+            friends_gateway,
+            Rc::new(FixedCalendar::new(
+                NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(),
+            )),
+            Rc::clone(&greetings_sender),
+        );
+        greeter.configure_milestones(vec![18, 30, 40, 50, 100]);
+
+        greeter.run().unwrap();
+
+        assert_eq!(
+            greetings_sender
+                .spied_sent_greetings()
+                .iter()
+                .map(|g| (g.friend_name.as_str(), g.age, g.milestone))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Aiko", Some(43), false),
+                ("John", Some(40), true),
+                ("Carla", None, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn notify_an_extra_recipient_of_milestone_birthdays() {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        friends_gateway.stub_friends(vec![
+            friend_living_in("Aiko", 8, 24, None),
+            FriendData::new(
+                "John",
+                "Sandri",
+                NaiveDate::from_ymd_opt(1973, 8, 24).unwrap(),
+                "john-sandri@email.com",
+                "3335556668",
+            ),
+        ]);
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let mut greeter = GreeterService::new(
+            friends_gateway,
+            Rc::new(FixedCalendar::new(
+                NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(),
+            )),
+            Rc::clone(&greetings_sender),
+        );
+        greeter.configure_milestones(vec![50]);
+        greeter.configure_milestone_recipient(ReminderRecipient::person(
+            "Anna",
+            "Bianchi",
+            "anna@bianchi.com",
+            "3330001112",
+        ));
+
+        greeter.run().unwrap();
+
+        assert_eq!(
+            greetings_sender.spied_sent_greetings()[2..],
+            vec![Greeting::new(
+                &FriendId::new("anna@bianchi.com"),
+                "Anna",
+                "Bianchi",
+                "anna@bianchi.com",
+                "3330001112"
+            )
+            .with_kind(GreetingKind::Milestone(Milestone::new(
+                "John", "Sandri", 50
+            )))]
+        );
+    }
+
+    #[test]
+    fn greet_friends_in_their_locale() {
+        let friends_gateway = Rc::new(FriendsGatewayTestDouble::new());
+        let locale = Locale::parse("it-CH").unwrap();
+        friends_gateway.stub_friends(vec![
+            friend_living_in("Aiko", 8, 24, None).with_locale(locale.clone())
+        ]);
+        let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+        let greeter = GreeterService::new(
+            friends_gateway,
+            Rc::new(FixedCalendar::new(
+                NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(),
+            )),
+            Rc::clone(&greetings_sender),
+        );
+
+        greeter.run().unwrap();
+
+        assert_eq!(
+            greetings_sender.spied_sent_greetings()[0].locale,
+            Some(locale)
+        );
+    }
 }
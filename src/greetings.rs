@@ -3,4 +3,6 @@ pub mod greeting;
 pub mod greetings_sender;
 pub mod greetings_sender_announcer;
 pub mod log_greetings_sender;
+pub mod sender_identity;
 pub mod smss;
+pub mod templates;
@@ -1,3 +1,7 @@
 pub mod email;
 pub mod email_greetings_sender;
+pub mod html_text;
+pub mod inline_image;
 pub mod mailer;
+pub mod mime_message;
+pub mod mime_part;
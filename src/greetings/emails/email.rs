@@ -1,9 +1,15 @@
+use super::{html_text::html_to_text, inline_image::InlineImage, mime_part::MimePart};
+
 #[derive(Eq, Hash, PartialEq, Debug, Clone)]
 pub struct Email {
     pub from: String,
     pub to: String,
     pub subject: String,
     pub text_body: String,
+    pub from_name: Option<String>,
+    pub reply_to: Option<String>,
+    pub html_body: Option<String>,
+    pub inline_images: Vec<InlineImage>,
 }
 
 impl Email {
@@ -13,6 +19,59 @@ impl Email {
             to: to.to_owned(),
             subject: subject.to_owned(),
             text_body: text_body.to_owned(),
+            from_name: None,
+            reply_to: None,
+            html_body: None,
+            inline_images: Vec::new(),
+        }
+    }
+
+    pub fn with_from_name(self, from_name: &str) -> Self {
+        Self {
+            from_name: Some(from_name.to_owned()),
+            ..self
+        }
+    }
+
+    pub fn with_reply_to(self, reply_to: &str) -> Self {
+        Self {
+            reply_to: Some(reply_to.to_owned()),
+            ..self
+        }
+    }
+
+    // A blank text body falls back to the text of the HTML
+    pub fn with_html_body(self, html_body: &str) -> Self {
+        let text_body = if self.text_body.trim().is_empty() {
+            html_to_text(html_body)
+        } else {
+            self.text_body
+        };
+        Self {
+            text_body,
+            html_body: Some(html_body.to_owned()),
+            ..self
+        }
+    }
+
+    pub fn with_inline_image(mut self, image: InlineImage) -> Self {
+        self.inline_images.push(image);
+        self
+    }
+
+    // Text only, or text and HTML alternatives with the HTML related to its inline images
+    pub fn body(&self) -> MimePart {
+        let text = MimePart::Text(self.text_body.clone());
+        let Some(html_body) = &self.html_body else {
+            return text;
+        };
+        let html = MimePart::Html(html_body.clone());
+        if self.inline_images.is_empty() {
+            return MimePart::Alternative(vec![text, html]);
         }
+        let related = std::iter::once(html)
+            .chain(self.inline_images.iter().cloned().map(MimePart::Image))
+            .collect();
+        MimePart::Alternative(vec![text, MimePart::Related(related)])
     }
 }
@@ -1,37 +1,227 @@
-use crate::greetings::{greeting::Greeting, greetings_sender::GreetingsSender};
+use crate::greetings::{
+    greeting::Greeting,
+    greetings_sender::{GreetingsSender, SendGreetingsError, SendSingleGreetingError},
+    sender_identity::EmailIdentity,
+    templates::{
+        message_templates::MessageTemplates, template::TemplateError,
+        translation_catalog::TranslationCatalog,
+    },
+};
 
-use super::{email::Email, mailer::Mailer};
+use super::{email::Email, inline_image::InlineImage, mailer::Mailer};
+use crate::friends::friend_data::Locale;
 use std::rc::Rc;
 
+const MESSAGES: &[&str] = &["subject"];
+const BODY_MESSAGES: &[&str] = &["body", "html"];
+
+const DEFAULT_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "subject",
+        "Happy {if belated}belated {end}{if milestone}{age|ordinal} {end}birthday!",
+    ),
+    (
+        "body",
+        "Happy {if belated}belated {end}{if milestone}{age|ordinal} {end}birthday, dear {name}!",
+    ),
+    (
+        "reminder_subject",
+        "{if today}Birthday reminder{else}Reminder: {friends_possessive} birthday{end}",
+    ),
+    (
+        "reminder_body",
+        "{if today}Dear {name},\nToday is {friends_possessive} birthday.\
+         {else}Dear {name}, {friends} {if several}celebrate{else}celebrates{end} their birthday on {date}.\
+         {end} Don't forget to send them a message!",
+    ),
+    (
+        "milestone_subject",
+        "{friend_name} {friend_surname} turns {age} today",
+    ),
+    (
+        "milestone_body",
+        "Dear {name},\nToday {friend_name} {friend_surname} turns {age}. Don't forget to send them a message!",
+    ),
+];
+
+const ITALIAN_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "subject",
+        "Buon {if milestone}{age}° {end}compleanno{if belated} in ritardo{end}!",
+    ),
+    (
+        "body",
+        "Buon {if milestone}{age}° {end}compleanno{if belated} in ritardo{end}, {name}!",
+    ),
+    (
+        "reminder_subject",
+        "{if today}Promemoria compleanno{else}Promemoria: compleanno di {friends}{end}",
+    ),
+    (
+        "reminder_body",
+        "{if today}Ciao {name},\noggi è il compleanno di {friends}.\
+         {else}Ciao {name}, il compleanno di {friends} è il {date}.\
+         {end} Non dimenticare di mandare un messaggio!",
+    ),
+    (
+        "milestone_subject",
+        "Oggi {friend_name} {friend_surname} compie {age} anni",
+    ),
+    (
+        "milestone_body",
+        "Ciao {name},\noggi {friend_name} {friend_surname} compie {age} anni. Non dimenticare di mandare un messaggio!",
+    ),
+];
+
+const GERMAN_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "subject",
+        "Alles Gute {if belated}nachträglich {end}zum {if milestone}{age}. {end}Geburtstag!",
+    ),
+    (
+        "body",
+        "Alles Gute {if belated}nachträglich {end}zum {if milestone}{age}. {end}Geburtstag, {name}!",
+    ),
+    (
+        "reminder_subject",
+        "{if today}Geburtstagserinnerung{else}Erinnerung: Geburtstag von {friends}{end}",
+    ),
+    (
+        "reminder_body",
+        "{if today}Hallo {name},\nheute {if several}haben{else}hat{end} {friends} Geburtstag.\
+         {else}Hallo {name}, {friends} {if several}haben{else}hat{end} am {date} Geburtstag.\
+         {end} Vergiss nicht, eine Nachricht zu schicken!",
+    ),
+    (
+        "milestone_subject",
+        "{friend_name} {friend_surname} wird heute {age}",
+    ),
+    (
+        "milestone_body",
+        "Hallo {name},\nheute wird {friend_name} {friend_surname} {age}. Vergiss nicht, eine Nachricht zu schicken!",
+    ),
+];
+
 pub struct EmailGreetingsSender {
     mailer: Rc<dyn Mailer>,
+    templates: TranslationCatalog,
+    identity: EmailIdentity,
+    inline_images: Vec<InlineImage>,
 }
 
 impl EmailGreetingsSender {
     pub fn new(mailer: Rc<impl Mailer + 'static>) -> Self {
-        Self { mailer }
+        Self {
+            mailer,
+            templates: Self::default_templates(),
+            identity: EmailIdentity::default(),
+            inline_images: Vec::new(),
+        }
+    }
+
+    fn default_templates() -> TranslationCatalog {
+        let templates = |sources| {
+            MessageTemplates::from_sources(sources).expect("default email templates are valid")
+        };
+        TranslationCatalog::new(templates(DEFAULT_TEMPLATES))
+            .with_translation(
+                Locale::parse("it").expect("valid locale"),
+                templates(ITALIAN_TEMPLATES),
+            )
+            .with_translation(
+                Locale::parse("de").expect("valid locale"),
+                templates(GERMAN_TEMPLATES),
+            )
+    }
+
+    pub fn configure_identity(&mut self, identity: EmailIdentity) {
+        self.identity = identity
+    }
+
+    // Images the "html" templates refer to as "cid:<content id>"
+    pub fn configure_inline_images(&mut self, inline_images: Vec<InlineImage>) {
+        self.inline_images = inline_images
+    }
+
+    // Templates need a "subject" and a "body" or an "html", plus their "reminder_" and "milestone_" variants.
+    // Without a "body" the text is derived from the "html"
+    pub fn configure_templates(
+        &mut self,
+        templates: MessageTemplates,
+    ) -> Result<(), TemplateError> {
+        self.configure_translations(TranslationCatalog::new(templates))
+    }
+
+    pub fn configure_translations(
+        &mut self,
+        translations: TranslationCatalog,
+    ) -> Result<(), TemplateError> {
+        translations.validate(MESSAGES)?;
+        translations.validate_one_of(BODY_MESSAGES)?;
+        self.templates = translations;
+        Ok(())
+    }
+
+    fn email_for(&self, greeting: &Greeting) -> Result<Email, TemplateError> {
+        let templates = self.templates.templates_for(greeting, |templates| {
+            MESSAGES
+                .iter()
+                .all(|message| templates.has_message(message, greeting))
+                && BODY_MESSAGES
+                    .iter()
+                    .any(|message| templates.has_message(message, greeting))
+        });
+        let text_body = if templates.has_message("body", greeting) {
+            templates.render("body", greeting)?
+        } else {
+            String::new()
+        };
+        let email = Email::new(
+            self.identity.address(),
+            &greeting.email,
+            &templates.render("subject", greeting)?,
+            &text_body,
+        );
+        let email = match self.identity.display_name() {
+            Some(display_name) => email.with_from_name(display_name),
+            None => email,
+        };
+        let email = match self.identity.reply_to() {
+            Some(reply_to) => email.with_reply_to(reply_to),
+            None => email,
+        };
+        if !templates.has_message("html", greeting) {
+            return Ok(email);
+        }
+        let html_body = templates.render("html", greeting)?;
+        Ok(self
+            .inline_images
+            .iter()
+            .filter(|image| html_body.contains(&format!("cid:{}", image.content_id())))
+            .cloned()
+            .fold(email.with_html_body(&html_body), Email::with_inline_image))
     }
 }
 
 impl GreetingsSender for EmailGreetingsSender {
-    fn send(
-        &self,
-        greetings: Vec<Greeting>,
-    ) -> Result<(), crate::greetings::greetings_sender::SendGreetingsError> {
-        let emails: Vec<Email> = greetings
-            .iter()
-            .map(|g| {
-                Email::new(
-                    "greeting@service.com",
-                    &g.email,
-                    "Happy birthday!",
-                    format!("Happy birthday, dear {0}!", g.friend_name).as_str(),
-                )
-            })
-            .collect();
+    fn send(&self, greetings: Vec<Greeting>) -> Result<(), SendGreetingsError> {
+        let mut emails = vec![];
+        let mut greetings_not_sent = vec![];
+        for greeting in greetings {
+            match self.email_for(&greeting) {
+                Ok(email) => emails.push(email),
+                Err(e) => {
+                    greetings_not_sent.push((greeting, SendSingleGreetingError::new(e.to_string())))
+                }
+            }
+        }
 
         // Mailer send failures should be handled properly
         self.mailer.send(emails);
-        Ok(())
+        if greetings_not_sent.is_empty() {
+            Ok(())
+        } else {
+            Err(SendGreetingsError::new(greetings_not_sent))
+        }
     }
 }
@@ -0,0 +1,187 @@
+// Plain text rendering of an HTML body, for mail clients that don't show HTML
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut skipped_element: Option<String> = None;
+    let mut link: Option<(String, usize)> = None;
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        if skipped_element.is_none() {
+            push_text(&mut text, &rest[..start]);
+        }
+        rest = &rest[start..];
+        let starts_tag = rest[1..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '!');
+        if !starts_tag {
+            if skipped_element.is_none() {
+                text.push('<');
+            }
+            rest = &rest[1..];
+            continue;
+        }
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if let Some(skipped) = &skipped_element {
+            if closing && *skipped == name {
+                skipped_element = None;
+            }
+            continue;
+        }
+        match name.as_str() {
+            "head" | "script" | "style" | "title" if !closing && !tag.ends_with('/') => {
+                skipped_element = Some(name)
+            }
+            "br" => text.push('\n'),
+            "p" | "div" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "ul" | "ol" | "table"
+            | "blockquote" | "hr" => text.push_str("\n\n"),
+            "li" if !closing => text.push_str("\n- "),
+            "tr" => text.push('\n'),
+            "td" | "th" if closing => text.push(' '),
+            "img" if !closing => {
+                if let Some(alt) = attribute(tag, "alt") {
+                    text.push_str(&decode_entities(&alt));
+                }
+            }
+            "a" if !closing => {
+                link = attribute(tag, "href")
+                    .map(|href| decode_entities(&href))
+                    .filter(|href| href.starts_with("http://") || href.starts_with("https://"))
+                    .map(|href| (href, text.len()))
+            }
+            "a" => {
+                if let Some((href, anchor_start)) = link.take() {
+                    if text[anchor_start..].trim() != href {
+                        text.push_str(&format!(" ({})", href));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    if skipped_element.is_none() {
+        push_text(&mut text, rest);
+    }
+    normalize_lines(&text)
+}
+
+// Whitespace in HTML text collapses into single spaces
+fn push_text(text: &mut String, html_text: &str) {
+    let mut previous_was_space = text.ends_with([' ', '\n']);
+    for c in decode_entities(html_text).chars() {
+        if c.is_whitespace() {
+            if !previous_was_space {
+                text.push(' ');
+            }
+            previous_was_space = true;
+        } else {
+            text.push(c);
+            previous_was_space = false;
+        }
+    }
+}
+
+// Trims every line and keeps at most one blank line between paragraphs
+fn normalize_lines(text: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if !line.is_empty() || lines.last().is_some_and(|last| !last.is_empty()) {
+            lines.push(line);
+        }
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let mut rest = tag.split_once(char::is_whitespace)?.1;
+    loop {
+        rest = rest.trim_start();
+        let end = rest.find(|c: char| c == '=' || c.is_whitespace() || c == '/' || c == '>')?;
+        let (attribute_name, after_name) = rest.split_at(end);
+        let after_name = after_name.trim_start();
+        let Some(value) = after_name.strip_prefix('=') else {
+            rest = after_name.strip_prefix('/').unwrap_or(after_name);
+            if after_name.is_empty() {
+                return None;
+            }
+            continue;
+        };
+        let value = value.trim_start();
+        let (attribute_value, after_value) = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let value = &value[1..];
+                let end = value.find(quote).unwrap_or(value.len());
+                (&value[..end], value.get(end + 1..).unwrap_or_default())
+            }
+            _ => {
+                let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                value.split_at(end)
+            }
+        };
+        if attribute_name.eq_ignore_ascii_case(name) {
+            return Some(attribute_value.to_owned());
+        }
+        rest = after_value;
+    }
+}
+
+fn decode_entities(html_text: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html_text;
+    while let Some(start) = rest.find('&') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end]).map(|c| (c, end)));
+        match decoded {
+            Some((c, end)) => {
+                text.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                text.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let number = entity.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
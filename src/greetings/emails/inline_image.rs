@@ -0,0 +1,100 @@
+use std::{fmt::Display, fs, path::Path};
+
+// An image the HTML body shows through "cid:<content id>", like a birthday card
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct InlineImage {
+    content_id: String,
+    content_type: String,
+    data: Vec<u8>,
+}
+
+impl InlineImage {
+    pub fn new(
+        content_id: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> Result<Self, InlineImageError> {
+        let is_valid_content_id = !content_id.is_empty()
+            && content_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || ".-_@+".contains(c));
+        if !is_valid_content_id {
+            return Err(InlineImageError::InvalidContentId(content_id.to_owned()));
+        }
+        let is_image_type = content_type.strip_prefix("image/").is_some_and(|subtype| {
+            !subtype.is_empty()
+                && subtype
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || ".-+".contains(c))
+        });
+        if !is_image_type {
+            return Err(InlineImageError::UnsupportedContentType(
+                content_type.to_owned(),
+            ));
+        }
+        Ok(Self {
+            content_id: content_id.to_owned(),
+            content_type: content_type.to_ascii_lowercase(),
+            data,
+        })
+    }
+
+    // The content type follows the file extension
+    pub fn load(content_id: &str, path: impl AsRef<Path>) -> Result<Self, InlineImageError> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let content_type = match extension.as_str() {
+            "png" => "image/png",
+            "jpg" | "jpeg" => "image/jpeg",
+            "gif" => "image/gif",
+            "webp" => "image/webp",
+            "svg" => "image/svg+xml",
+            _ => {
+                return Err(InlineImageError::UnsupportedContentType(
+                    path.display().to_string(),
+                ))
+            }
+        };
+        let data = fs::read(path).map_err(|e| InlineImageError::Io(e.to_string()))?;
+        Self::new(content_id, content_type, data)
+    }
+
+    pub fn content_id(&self) -> &str {
+        &self.content_id
+    }
+
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InlineImageError {
+    Io(String),
+    InvalidContentId(String),
+    UnsupportedContentType(String),
+}
+
+impl Display for InlineImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InlineImageError::Io(message) => write!(f, "unable to read image: {}", message),
+            InlineImageError::InvalidContentId(content_id) => {
+                write!(f, "invalid content id '{}'", content_id)
+            }
+            InlineImageError::UnsupportedContentType(content_type) => {
+                write!(f, "unsupported image type '{}'", content_type)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InlineImageError {}
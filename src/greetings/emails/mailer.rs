@@ -1,5 +1,6 @@
 use super::email::Email;
 
+// Mailers serialize the MIME structure of each email, see Email::body
 pub trait Mailer {
     fn send(&self, emails: Vec<Email>);
 }
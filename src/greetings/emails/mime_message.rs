@@ -0,0 +1,280 @@
+use chrono::{DateTime, FixedOffset};
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use super::{email::Email, mime_part::MimePart};
+use crate::greetings::sender_identity::is_valid_address;
+
+const MAX_HEADER_LINE_LENGTH: usize = 78;
+const MAX_BODY_LINE_LENGTH: usize = 76;
+// 42 bytes encode to 56 characters, so "Subject: =?UTF-8?B?...?=" stays within 78
+const MAX_ENCODED_WORD_BYTES: usize = 42;
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+static MESSAGE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+// Unique within the domain of the sender, like "1679644800000000.0.4242@service.com"
+pub fn new_message_id(email: &Email, date: &DateTime<FixedOffset>) -> String {
+    let domain = email
+        .from
+        .rsplit_once('@')
+        .map_or("localhost.localdomain", |(_, domain)| domain);
+    format!(
+        "{}.{}.{}@{}",
+        date.timestamp_micros(),
+        MESSAGE_COUNTER.fetch_add(1, Ordering::Relaxed),
+        std::process::id(),
+        domain
+    )
+}
+
+// The RFC 5322 message of the email, with its MIME body and CRLF line endings.
+// The message id goes without angle brackets.
+// Addresses are checked, as one carrying a line break would add headers of its own
+pub fn to_mime_message(
+    email: &Email,
+    date: &DateTime<FixedOffset>,
+    message_id: &str,
+) -> Result<Vec<u8>, MimeMessageError> {
+    for address in [&email.from, &email.to].into_iter().chain(&email.reply_to) {
+        if !is_valid_address(address) {
+            return Err(MimeMessageError::InvalidAddress(address.clone()));
+        }
+    }
+    if !is_valid_address(message_id) {
+        return Err(MimeMessageError::InvalidMessageId(message_id.to_owned()));
+    }
+    let mut message = String::new();
+    write_header(&mut message, "Date", vec![date.to_rfc2822()]);
+    write_header(
+        &mut message,
+        "Message-ID",
+        vec![format!("<{}>", message_id)],
+    );
+    write_header(
+        &mut message,
+        "From",
+        mailbox(email.from_name.as_deref(), &email.from),
+    );
+    if let Some(reply_to) = &email.reply_to {
+        write_header(&mut message, "Reply-To", vec![reply_to.clone()]);
+    }
+    write_header(&mut message, "To", vec![email.to.clone()]);
+    write_header(&mut message, "Subject", unstructured(&email.subject));
+    write_header(&mut message, "MIME-Version", vec!["1.0".to_owned()]);
+    write_part(&mut message, &email.body(), &mut 0);
+    Ok(message.into_bytes())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MimeMessageError {
+    InvalidAddress(String),
+    InvalidMessageId(String),
+}
+
+impl Display for MimeMessageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MimeMessageError::InvalidAddress(address) => {
+                write!(f, "invalid email address '{}'", address)
+            }
+            MimeMessageError::InvalidMessageId(message_id) => {
+                write!(f, "invalid message id '{}'", message_id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MimeMessageError {}
+
+// Folds between the space separated words of the value to keep lines short
+fn write_header(message: &mut String, name: &str, words: Vec<String>) {
+    let mut line_length = name.len() + 1;
+    message.push_str(name);
+    message.push(':');
+    for (index, word) in words.iter().enumerate() {
+        if index > 0 && line_length + 1 + word.len() > MAX_HEADER_LINE_LENGTH {
+            message.push_str("\r\n");
+            line_length = 0;
+        }
+        message.push(' ');
+        message.push_str(word);
+        line_length += 1 + word.len();
+    }
+    message.push_str("\r\n");
+}
+
+fn unstructured(value: &str) -> Vec<String> {
+    if needs_encoding(value) {
+        encoded_words(value)
+    } else {
+        value.split(' ').map(str::to_owned).collect()
+    }
+}
+
+// "Name <address>", quoting or encoding the display name when needed
+fn mailbox(display_name: Option<&str>, address: &str) -> Vec<String> {
+    let mut words = match display_name {
+        None => return vec![address.to_owned()],
+        Some(name) if needs_encoding(name) => encoded_words(name),
+        Some(name) if name.chars().all(|c| is_atext(c) || c == ' ') => {
+            name.split(' ').map(str::to_owned).collect()
+        }
+        Some(name) => {
+            let quoted = name.replace('\\', "\\\\").replace('"', "\\\"");
+            format!("\"{}\"", quoted)
+                .split(' ')
+                .map(str::to_owned)
+                .collect()
+        }
+    };
+    words.push(format!("<{}>", address));
+    words
+}
+
+fn needs_encoding(value: &str) -> bool {
+    value.chars().any(|c| !c.is_ascii() || c.is_ascii_control())
+        || value.contains("=?")
+        || value
+            .split(' ')
+            .any(|word| word.len() > MAX_HEADER_LINE_LENGTH)
+}
+
+fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c)
+}
+
+// RFC 2047 encoded words, never splitting a character across two of them
+fn encoded_words(value: &str) -> Vec<String> {
+    let mut chunks = vec![String::new()];
+    for c in value.chars() {
+        let chunk = chunks.last_mut().expect("there is always a chunk");
+        if chunk.len() + c.len_utf8() > MAX_ENCODED_WORD_BYTES {
+            chunks.push(c.to_string());
+        } else {
+            chunk.push(c);
+        }
+    }
+    chunks
+        .iter()
+        .map(|chunk| format!("=?UTF-8?B?{}?=", base64(chunk.as_bytes())))
+        .collect()
+}
+
+fn write_part(message: &mut String, part: &MimePart, boundaries: &mut usize) {
+    match part {
+        MimePart::Text(text) | MimePart::Html(text) => {
+            let (encoding, body) = encode_text(text);
+            message.push_str(&format!(
+                "Content-Type: {}; charset=utf-8\r\nContent-Transfer-Encoding: {}\r\n\r\n{}",
+                part.content_type(),
+                encoding,
+                body
+            ));
+        }
+        MimePart::Image(image) => {
+            message.push_str(&format!(
+                "Content-Type: {}\r\nContent-Transfer-Encoding: base64\r\nContent-ID: <{}>\r\nContent-Disposition: inline\r\n\r\n",
+                image.content_type(),
+                image.content_id()
+            ));
+            let encoded = base64(image.data());
+            for line in encoded.as_bytes().chunks(MAX_BODY_LINE_LENGTH) {
+                message.push_str(std::str::from_utf8(line).expect("base64 is ascii"));
+                message.push_str("\r\n");
+            }
+        }
+        MimePart::Alternative(parts) | MimePart::Related(parts) => {
+            // Encoded bodies never contain "=_", so boundaries can't clash with them
+            let boundary = format!("=_Part_{}", boundaries);
+            *boundaries += 1;
+            message.push_str(&format!(
+                "Content-Type: {}; boundary=\"{}\"",
+                part.content_type(),
+                boundary
+            ));
+            if let (MimePart::Related(_), Some(root)) = (part, parts.first()) {
+                message.push_str(&format!("; type=\"{}\"", root.content_type()));
+            }
+            message.push_str("\r\n\r\n");
+            for part in parts {
+                message.push_str(&format!("--{}\r\n", boundary));
+                write_part(message, part, boundaries);
+            }
+            message.push_str(&format!("--{}--\r\n", boundary));
+        }
+    }
+}
+
+// Short ASCII lines go as they are, anything else as quoted-printable
+fn encode_text(text: &str) -> (&'static str, String) {
+    let text = &text.replace("\r\n", "\n");
+    let is_7bit = text
+        .chars()
+        .all(|c| c.is_ascii() && (!c.is_ascii_control() || "\t\n".contains(c)))
+        && text.lines().all(|line| {
+            line.len() <= MAX_BODY_LINE_LENGTH && !line.starts_with("--=_") && !line.ends_with(' ')
+        });
+    if is_7bit {
+        (
+            "7bit",
+            text.lines().map(|line| format!("{}\r\n", line)).collect(),
+        )
+    } else {
+        ("quoted-printable", quoted_printable(text))
+    }
+}
+
+fn quoted_printable(text: &str) -> String {
+    let mut encoded = String::new();
+    for line in text.lines() {
+        let bytes = line.as_bytes();
+        let mut encoded_line = String::new();
+        for (index, byte) in bytes.iter().enumerate() {
+            let is_last = index == bytes.len() - 1;
+            let literal = (b'!'..=b'~').contains(byte) && *byte != b'='
+                || (*byte == b' ' || *byte == b'\t') && !is_last;
+            let token = if literal {
+                (*byte as char).to_string()
+            } else {
+                format!("={:02X}", byte)
+            };
+            // Leave room for the "=" of a soft line break unless the line ends here
+            let limit = if is_last {
+                MAX_BODY_LINE_LENGTH
+            } else {
+                MAX_BODY_LINE_LENGTH - 1
+            };
+            if encoded_line.len() + token.len() > limit {
+                encoded.push_str(&encoded_line);
+                encoded.push_str("=\r\n");
+                encoded_line.clear();
+            }
+            encoded_line.push_str(&token);
+        }
+        encoded.push_str(&encoded_line);
+        encoded.push_str("\r\n");
+    }
+    encoded
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (index, byte)| {
+            group | (*byte as u32) << (16 - 8 * index)
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (group >> (18 - 6 * index)) & 0x3f;
+                encoded.push(BASE64_ALPHABET[sextet as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
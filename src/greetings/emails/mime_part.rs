@@ -0,0 +1,23 @@
+use super::inline_image::InlineImage;
+
+// The structure of an email body, ready to be serialized as MIME
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub enum MimePart {
+    Text(String),
+    Html(String),
+    Image(InlineImage),
+    Alternative(Vec<MimePart>),
+    Related(Vec<MimePart>),
+}
+
+impl MimePart {
+    pub fn content_type(&self) -> &str {
+        match self {
+            MimePart::Text(_) => "text/plain",
+            MimePart::Html(_) => "text/html",
+            MimePart::Image(image) => image.content_type(),
+            MimePart::Alternative(_) => "multipart/alternative",
+            MimePart::Related(_) => "multipart/related",
+        }
+    }
+}
@@ -1,18 +1,162 @@
+use chrono::NaiveDate;
+
+use crate::friends::{
+    birthdate::Birthdate,
+    friend_data::{FriendId, Locale},
+};
+
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub enum GreetingKind {
+    Birthday,
+    BelatedBirthday,
+    Reminder(Reminder),
+    Milestone(Milestone),
+}
+
+// A reminder is addressed to someone else than the friends about to celebrate
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct Reminder {
+    pub friends: Vec<(String, String)>,
+    pub birthday: NaiveDate,
+    pub days_before: u32,
+}
+
+impl Reminder {
+    pub fn new(birthday: NaiveDate, days_before: u32) -> Self {
+        Self {
+            friends: Vec::new(),
+            birthday,
+            days_before,
+        }
+    }
+
+    pub fn with_friend(mut self, friend_name: &str, friend_surname: &str) -> Self {
+        self.friends
+            .push((friend_name.to_owned(), friend_surname.to_owned()));
+        self
+    }
+
+    // "Mary Ann, John Doe and Lisa Bell"
+    pub fn friend_names(&self) -> String {
+        self.friend_names_joined_by("and")
+    }
+
+    // "Mary Ann's, John Doe's and Lisa Bell's"
+    pub fn friend_names_possessive(&self) -> String {
+        Self::enumerate(
+            self.friends.iter().map(|(n, s)| format!("{} {}'s", n, s)),
+            "and",
+        )
+    }
+
+    pub fn friend_names_joined_by(&self, conjunction: &str) -> String {
+        Self::enumerate(
+            self.friends.iter().map(|(n, s)| format!("{} {}", n, s)),
+            conjunction,
+        )
+    }
+
+    fn enumerate(names: impl Iterator<Item = String>, conjunction: &str) -> String {
+        let mut names: Vec<String> = names.collect();
+        match names.pop() {
+            Some(last) if !names.is_empty() => {
+                format!("{} {} {}", names.join(", "), conjunction, last)
+            }
+            Some(last) => last,
+            None => String::new(),
+        }
+    }
+}
+
+// A milestone birthday announced to someone else than the friend celebrating it
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct Milestone {
+    pub friend_name: String,
+    pub friend_surname: String,
+    pub age: u32,
+}
+
+impl Milestone {
+    pub fn new(friend_name: &str, friend_surname: &str, age: u32) -> Self {
+        Self {
+            friend_name: friend_name.to_owned(),
+            friend_surname: friend_surname.to_owned(),
+            age,
+        }
+    }
+}
+
+// "1st", "2nd", "3rd", "11th", "40th"
+pub fn ordinal(number: u32) -> String {
+    let suffix = match (number % 10, number % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", number, suffix)
+}
+
 #[derive(Eq, Hash, PartialEq, Debug, Clone)]
 pub struct Greeting {
+    pub friend_id: FriendId,
     pub friend_name: String,
     pub friend_surname: String,
     pub email: String,
     pub phone_number: String,
+    pub kind: GreetingKind,
+    pub birthdate: Option<Birthdate>,
+    pub age: Option<u32>,
+    pub milestone: bool,
+    pub locale: Option<Locale>,
 }
 
 impl Greeting {
-    pub fn new(friend_name: &str, friend_surname: &str, email: &str, phone_number: &str) -> Self {
+    pub fn new(
+        friend_id: &FriendId,
+        friend_name: &str,
+        friend_surname: &str,
+        email: &str,
+        phone_number: &str,
+    ) -> Self {
         Self {
+            friend_id: friend_id.clone(),
             friend_name: friend_name.to_owned(),
             friend_surname: friend_surname.to_owned(),
             email: email.to_owned(),
             phone_number: phone_number.to_owned(),
+            kind: GreetingKind::Birthday,
+            birthdate: None,
+            age: None,
+            milestone: false,
+            locale: None,
+        }
+    }
+
+    pub fn with_kind(self, kind: GreetingKind) -> Self {
+        Self { kind, ..self }
+    }
+
+    pub fn with_birthdate(self, birthdate: Birthdate) -> Self {
+        Self {
+            birthdate: Some(birthdate),
+            ..self
         }
     }
+
+    pub fn with_age(self, age: u32) -> Self {
+        Self {
+            age: Some(age),
+            ..self
+        }
+    }
+
+    pub fn with_milestone(self, milestone: bool) -> Self {
+        Self { milestone, ..self }
+    }
+
+    pub fn with_locale(self, locale: Option<Locale>) -> Self {
+        Self { locale, ..self }
+    }
 }
@@ -0,0 +1,161 @@
+use std::fmt::Display;
+
+const DEFAULT_EMAIL_ADDRESS: &str = "greeting@service.com";
+const DEFAULT_SMS_SENDER_ID: &str = "3334445551";
+
+// Who greetings sent by email come from
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct EmailIdentity {
+    address: String,
+    display_name: Option<String>,
+    reply_to: Option<String>,
+}
+
+impl EmailIdentity {
+    pub fn new(address: &str) -> Result<Self, SenderIdentityError> {
+        Ok(Self {
+            address: validate_address(address)?,
+            display_name: None,
+            reply_to: None,
+        })
+    }
+
+    // Control characters would let a display name forge further headers
+    pub fn with_display_name(self, display_name: &str) -> Result<Self, SenderIdentityError> {
+        if display_name.trim().is_empty() || display_name.chars().any(char::is_control) {
+            return Err(SenderIdentityError::InvalidDisplayName(
+                display_name.to_owned(),
+            ));
+        }
+        Ok(Self {
+            display_name: Some(display_name.to_owned()),
+            ..self
+        })
+    }
+
+    pub fn with_reply_to(self, reply_to: &str) -> Result<Self, SenderIdentityError> {
+        Ok(Self {
+            reply_to: Some(validate_address(reply_to)?),
+            ..self
+        })
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    pub fn reply_to(&self) -> Option<&str> {
+        self.reply_to.as_deref()
+    }
+}
+
+impl Default for EmailIdentity {
+    fn default() -> Self {
+        Self {
+            address: DEFAULT_EMAIL_ADDRESS.to_owned(),
+            display_name: None,
+            reply_to: None,
+        }
+    }
+}
+
+// Who greetings sent by SMS come from: a phone number, a short code or an alphanumeric originator
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct SmsSenderId(String);
+
+impl SmsSenderId {
+    // "+41791234567", "3334445551" or a short code such as "4455"
+    pub fn phone_number(phone_number: &str) -> Result<Self, SenderIdentityError> {
+        let digits = phone_number.strip_prefix('+').unwrap_or(phone_number);
+        if !(3..=15).contains(&digits.len()) || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(SenderIdentityError::InvalidSenderId(
+                phone_number.to_owned(),
+            ));
+        }
+        Ok(Self(phone_number.to_owned()))
+    }
+
+    // Carriers accept up to 11 letters, digits and spaces, with at least a letter
+    pub fn alphanumeric(originator: &str) -> Result<Self, SenderIdentityError> {
+        let is_valid = (1..=11).contains(&originator.len())
+            && originator
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == ' ')
+            && originator.chars().any(|c| c.is_ascii_alphabetic())
+            && originator.trim() == originator;
+        if !is_valid {
+            return Err(SenderIdentityError::InvalidSenderId(originator.to_owned()));
+        }
+        Ok(Self(originator.to_owned()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for SmsSenderId {
+    fn default() -> Self {
+        Self(DEFAULT_SMS_SENDER_ID.to_owned())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SenderIdentityError {
+    InvalidAddress(String),
+    InvalidDisplayName(String),
+    InvalidSenderId(String),
+}
+
+impl Display for SenderIdentityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SenderIdentityError::InvalidAddress(value) => {
+                write!(f, "invalid email address '{}'", value)
+            }
+            SenderIdentityError::InvalidDisplayName(value) => {
+                write!(f, "invalid display name '{}'", value)
+            }
+            SenderIdentityError::InvalidSenderId(value) => {
+                write!(f, "invalid SMS sender id '{}'", value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SenderIdentityError {}
+
+fn validate_address(address: &str) -> Result<String, SenderIdentityError> {
+    if !is_valid_address(address) {
+        return Err(SenderIdentityError::InvalidAddress(address.to_owned()));
+    }
+    Ok(address.to_owned())
+}
+
+// A pragmatic subset of RFC 5322 addresses: dot-atom local part and a domain name
+pub(crate) fn is_valid_address(address: &str) -> bool {
+    let Some((local_part, domain)) = address.rsplit_once('@') else {
+        return false;
+    };
+    let is_dot_atom = |value: &str, is_atom_char: fn(char) -> bool| {
+        !value.is_empty()
+            && value
+                .split('.')
+                .all(|atom| !atom.is_empty() && atom.chars().all(is_atom_char))
+    };
+    let local_part_is_valid = local_part.len() <= 64
+        && is_dot_atom(local_part, |c| {
+            c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~-".contains(c)
+        });
+    let domain_is_valid = domain.len() <= 253
+        && domain.contains('.')
+        && is_dot_atom(domain, |c| c.is_ascii_alphanumeric() || c == '-')
+        && domain
+            .split('.')
+            .all(|label| label.len() <= 63 && !label.starts_with('-') && !label.ends_with('-'));
+    local_part_is_valid && domain_is_valid
+}
@@ -1,36 +1,147 @@
-use crate::greetings::{greeting::Greeting, greetings_sender::GreetingsSender};
+use crate::friends::friend_data::Locale;
+use crate::greetings::{
+    greeting::Greeting,
+    greetings_sender::{GreetingsSender, SendGreetingsError, SendSingleGreetingError},
+    sender_identity::SmsSenderId,
+    templates::{
+        message_templates::MessageTemplates, template::TemplateError,
+        translation_catalog::TranslationCatalog,
+    },
+};
 use std::rc::Rc;
 
 use super::{sms::Sms, sms_service::SmsService};
 
+const MESSAGES: &[&str] = &["text"];
+
+const DEFAULT_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "text",
+        "Happy {if belated}belated {end}{if milestone}{age|ordinal} {end}birthday, dear {name}!",
+    ),
+    (
+        "reminder_text",
+        "{if today}Today is {friends_possessive} birthday. Don't forget to send them a message!\
+         {else}Reminder: {friends_possessive} birthday is on {date}!{end}",
+    ),
+    (
+        "milestone_text",
+        "Today {friend_name} {friend_surname} turns {age}. Don't forget to send them a message!",
+    ),
+];
+
+const ITALIAN_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "text",
+        "Buon {if milestone}{age}° {end}compleanno{if belated} in ritardo{end}, {name}!",
+    ),
+    (
+        "reminder_text",
+        "{if today}Oggi è il compleanno di {friends}. Non dimenticare di mandare un messaggio!\
+         {else}Promemoria: il compleanno di {friends} è il {date}!{end}",
+    ),
+    (
+        "milestone_text",
+        "Oggi {friend_name} {friend_surname} compie {age} anni. Non dimenticare di mandare un messaggio!",
+    ),
+];
+
+const GERMAN_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "text",
+        "Alles Gute {if belated}nachträglich {end}zum {if milestone}{age}. {end}Geburtstag, {name}!",
+    ),
+    (
+        "reminder_text",
+        "{if today}Heute {if several}haben{else}hat{end} {friends} Geburtstag. Vergiss nicht, eine Nachricht zu schicken!\
+         {else}Erinnerung: {friends} {if several}haben{else}hat{end} am {date} Geburtstag!{end}",
+    ),
+    (
+        "milestone_text",
+        "Heute wird {friend_name} {friend_surname} {age}. Vergiss nicht, eine Nachricht zu schicken!",
+    ),
+];
+
 pub struct SmsGreetingsSender {
     sms_service: Rc<dyn SmsService>,
+    templates: TranslationCatalog,
+    sender_id: SmsSenderId,
 }
 
 impl SmsGreetingsSender {
     pub fn new(sms_service: Rc<impl SmsService + 'static>) -> Self {
-        Self { sms_service }
+        Self {
+            sms_service,
+            templates: Self::default_templates(),
+            sender_id: SmsSenderId::default(),
+        }
+    }
+
+    fn default_templates() -> TranslationCatalog {
+        let templates = |sources| {
+            MessageTemplates::from_sources(sources).expect("default SMS templates are valid")
+        };
+        TranslationCatalog::new(templates(DEFAULT_TEMPLATES))
+            .with_translation(
+                Locale::parse("it").expect("valid locale"),
+                templates(ITALIAN_TEMPLATES),
+            )
+            .with_translation(
+                Locale::parse("de").expect("valid locale"),
+                templates(GERMAN_TEMPLATES),
+            )
+    }
+
+    pub fn configure_sender_id(&mut self, sender_id: SmsSenderId) {
+        self.sender_id = sender_id
+    }
+
+    // Templates need a "text", plus its "reminder_" and "milestone_" variants
+    pub fn configure_templates(
+        &mut self,
+        templates: MessageTemplates,
+    ) -> Result<(), TemplateError> {
+        self.configure_translations(TranslationCatalog::new(templates))
+    }
+
+    pub fn configure_translations(
+        &mut self,
+        translations: TranslationCatalog,
+    ) -> Result<(), TemplateError> {
+        translations.validate(MESSAGES)?;
+        self.templates = translations;
+        Ok(())
     }
 }
 
 impl GreetingsSender for SmsGreetingsSender {
-    fn send(
-        &self,
-        greetings: Vec<Greeting>,
-    ) -> Result<(), crate::greetings::greetings_sender::SendGreetingsError> {
-        let emails: Vec<Sms> = greetings
-            .iter()
-            .map(|g| {
-                Sms::new(
-                    "3334445551",
-                    &g.phone_number,
-                    format!("Happy birthday, dear {0}!", g.friend_name).as_str(),
-                )
-            })
-            .collect();
+    fn send(&self, greetings: Vec<Greeting>) -> Result<(), SendGreetingsError> {
+        let mut smss = vec![];
+        let mut greetings_not_sent = vec![];
+        for greeting in greetings {
+            let templates = self.templates.templates_for(&greeting, |templates| {
+                MESSAGES
+                    .iter()
+                    .all(|message| templates.has_message(message, &greeting))
+            });
+            match templates.render("text", &greeting) {
+                Ok(text) => smss.push(Sms::new(
+                    self.sender_id.as_str(),
+                    &greeting.phone_number,
+                    &text,
+                )),
+                Err(e) => {
+                    greetings_not_sent.push((greeting, SendSingleGreetingError::new(e.to_string())))
+                }
+            }
+        }
 
         // SMS service send failures should be handled properly
-        self.sms_service.send(emails);
-        Ok(())
+        self.sms_service.send(smss);
+        if greetings_not_sent.is_empty() {
+            Ok(())
+        } else {
+            Err(SendGreetingsError::new(greetings_not_sent))
+        }
     }
 }
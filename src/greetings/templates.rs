@@ -0,0 +1,3 @@
+pub mod message_templates;
+pub mod template;
+pub mod translation_catalog;
@@ -0,0 +1,265 @@
+use chrono::Datelike;
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::greetings::greeting::{Greeting, GreetingKind};
+
+use super::template::{Template, TemplateError};
+
+const GREETING_PLACEHOLDERS: &[&str] = &[
+    "name",
+    "surname",
+    "age",
+    "birthdate",
+    "milestone",
+    "belated",
+];
+const REMINDER_PLACEHOLDERS: &[&str] = &[
+    "name",
+    "surname",
+    "friends",
+    "friends_possessive",
+    "date",
+    "today",
+    "several",
+];
+const MILESTONE_PLACEHOLDERS: &[&str] =
+    &["name", "surname", "friend_name", "friend_surname", "age"];
+
+// Each message ("subject", "body", "text"...) has a template per kind of greeting:
+// "body" for birthdays, "reminder_body" for reminders and "milestone_body" for milestone notices
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MessageTemplates {
+    templates: HashMap<String, Template>,
+}
+
+impl MessageTemplates {
+    pub fn from_sources(sources: &[(&str, &str)]) -> Result<Self, TemplateError> {
+        let templates = sources
+            .iter()
+            .map(|(name, source)| Ok((name.to_string(), Template::parse(name, source)?)))
+            .collect::<Result<HashMap<String, Template>, TemplateError>>()?;
+        Ok(Self { templates })
+    }
+
+    // Every "<name>.txt" file of the directory is the template of that name
+    pub fn load(directory: impl AsRef<Path>) -> Result<Self, TemplateError> {
+        let io_error = |e: std::io::Error| TemplateError::Io(e.to_string());
+        let mut templates = HashMap::new();
+        for entry in fs::read_dir(directory).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            if path.extension().is_none_or(|extension| extension != "txt") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let source = fs::read_to_string(&path).map_err(io_error)?;
+            let source = source
+                .strip_suffix('\n')
+                .map(|source| source.strip_suffix('\r').unwrap_or(source))
+                .unwrap_or(&source);
+            templates.insert(name.to_owned(), Template::parse(name, source)?);
+        }
+        Ok(Self { templates })
+    }
+
+    pub(crate) fn validate(&self, messages: &[&str]) -> Result<(), TemplateError> {
+        for message in messages {
+            if let Some(name) = self.missing_template(message) {
+                return Err(TemplateError::MissingTemplate(name));
+            }
+        }
+        self.validate_placeholders()
+    }
+
+    // At least one of the messages needs all its templates, like an email "body" or "html"
+    pub(crate) fn validate_one_of(&self, messages: &[&str]) -> Result<(), TemplateError> {
+        let missing_templates: Vec<String> = messages
+            .iter()
+            .filter_map(|message| self.missing_template(message))
+            .collect();
+        if missing_templates.len() == messages.len() {
+            if let Some(name) = missing_templates.into_iter().next() {
+                return Err(TemplateError::MissingTemplate(name));
+            }
+        }
+        self.validate_placeholders()
+    }
+
+    fn missing_template(&self, message: &str) -> Option<String> {
+        ["", "reminder_", "milestone_"]
+            .iter()
+            .map(|prefix| format!("{}{}", prefix, message))
+            .find(|name| !self.templates.contains_key(name))
+    }
+
+    pub(crate) fn validate_placeholders(&self) -> Result<(), TemplateError> {
+        for (name, template) in &self.templates {
+            let allowed_placeholders = if name.starts_with("reminder_") {
+                REMINDER_PLACEHOLDERS
+            } else if name.starts_with("milestone_") {
+                MILESTONE_PLACEHOLDERS
+            } else {
+                GREETING_PLACEHOLDERS
+            };
+            if let Some(placeholder) = template
+                .placeholders()
+                .into_iter()
+                .find(|p| !allowed_placeholders.contains(&p.as_str()))
+            {
+                return Err(TemplateError::UnknownPlaceholder {
+                    template: name.to_owned(),
+                    placeholder,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn has_message(&self, message: &str, greeting: &Greeting) -> bool {
+        self.templates
+            .contains_key(&Self::template_name(message, greeting))
+    }
+
+    fn template_name(message: &str, greeting: &Greeting) -> String {
+        match &greeting.kind {
+            GreetingKind::Birthday | GreetingKind::BelatedBirthday => message.to_owned(),
+            GreetingKind::Reminder(_) => format!("reminder_{}", message),
+            GreetingKind::Milestone(_) => format!("milestone_{}", message),
+        }
+    }
+
+    // Dates and enumerations follow the language of the greeting, whichever template renders it
+    pub(crate) fn render(
+        &self,
+        message: &str,
+        greeting: &Greeting,
+    ) -> Result<String, TemplateError> {
+        let name = Self::template_name(message, greeting);
+        let template = self
+            .templates
+            .get(&name)
+            .ok_or(TemplateError::MissingTemplate(name))?;
+        let language = greeting.locale.as_ref().map(|locale| locale.language());
+        let flag = |value: bool| if value { "true" } else { "" }.to_owned();
+        let mut context = HashMap::from([
+            ("name", greeting.friend_name.clone()),
+            ("surname", greeting.friend_surname.clone()),
+        ]);
+        match &greeting.kind {
+            GreetingKind::Birthday | GreetingKind::BelatedBirthday => {
+                let birthdate = greeting.birthdate.map(|birthdate| {
+                    format_date(
+                        birthdate.day(),
+                        birthdate.month(),
+                        birthdate.year(),
+                        language,
+                    )
+                });
+                context.extend([
+                    (
+                        "age",
+                        greeting.age.map(|age| age.to_string()).unwrap_or_default(),
+                    ),
+                    ("birthdate", birthdate.unwrap_or_default()),
+                    (
+                        "milestone",
+                        flag(greeting.milestone && greeting.age.is_some()),
+                    ),
+                    (
+                        "belated",
+                        flag(greeting.kind == GreetingKind::BelatedBirthday),
+                    ),
+                ]);
+            }
+            GreetingKind::Reminder(reminder) => {
+                let conjunction = match language {
+                    Some("it") => "e",
+                    Some("de") => "und",
+                    _ => "and",
+                };
+                let birthday = reminder.birthday;
+                context.extend([
+                    ("friends", reminder.friend_names_joined_by(conjunction)),
+                    ("friends_possessive", reminder.friend_names_possessive()),
+                    (
+                        "date",
+                        format_date(birthday.day(), birthday.month(), None, language),
+                    ),
+                    ("today", flag(reminder.days_before == 0)),
+                    ("several", flag(reminder.friends.len() > 1)),
+                ]);
+            }
+            GreetingKind::Milestone(milestone) => {
+                context.extend([
+                    ("friend_name", milestone.friend_name.clone()),
+                    ("friend_surname", milestone.friend_surname.clone()),
+                    ("age", milestone.age.to_string()),
+                ]);
+            }
+        }
+        if message == "html" {
+            Ok(template.render_html(&context))
+        } else {
+            Ok(template.render(&context))
+        }
+    }
+}
+
+const ENGLISH_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const ITALIAN_MONTHS: [&str; 12] = [
+    "gennaio",
+    "febbraio",
+    "marzo",
+    "aprile",
+    "maggio",
+    "giugno",
+    "luglio",
+    "agosto",
+    "settembre",
+    "ottobre",
+    "novembre",
+    "dicembre",
+];
+const GERMAN_MONTHS: [&str; 12] = [
+    "Januar",
+    "Februar",
+    "März",
+    "April",
+    "Mai",
+    "Juni",
+    "Juli",
+    "August",
+    "September",
+    "Oktober",
+    "November",
+    "Dezember",
+];
+
+// "August 24, 1970", "24 agosto 1970", "24. August 1970", or "24/08/1970" when the language is unknown
+fn format_date(day: u32, month: u32, year: Option<i32>, language: Option<&str>) -> String {
+    let month_name = |months: [&'static str; 12]| months[(month - 1) as usize];
+    match (language, year) {
+        (Some("en"), Some(year)) => format!("{} {}, {}", month_name(ENGLISH_MONTHS), day, year),
+        (Some("en"), None) => format!("{} {}", month_name(ENGLISH_MONTHS), day),
+        (Some("it"), Some(year)) => format!("{} {} {}", day, month_name(ITALIAN_MONTHS), year),
+        (Some("it"), None) => format!("{} {}", day, month_name(ITALIAN_MONTHS)),
+        (Some("de"), Some(year)) => format!("{}. {} {}", day, month_name(GERMAN_MONTHS), year),
+        (Some("de"), None) => format!("{}. {}", day, month_name(GERMAN_MONTHS)),
+        (_, Some(year)) => format!("{:02}/{:02}/{}", day, month, year),
+        (_, None) => format!("{:02}/{:02}", day, month),
+    }
+}
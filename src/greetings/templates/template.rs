@@ -0,0 +1,338 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::greetings::greeting::ordinal;
+
+// Renders "{name}", "{age|ordinal}" and "{if milestone}...{else}...{end}", with "{{" and "}}" as literal braces
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    name: String,
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Placeholder {
+        name: String,
+        filters: Vec<Filter>,
+    },
+    Conditional {
+        name: String,
+        negated: bool,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Filter {
+    Upper,
+    Lower,
+    Capitalize,
+    Ordinal,
+}
+
+enum Token {
+    Text(String),
+    Tag(Tag),
+}
+
+enum Tag {
+    Placeholder(String, Vec<Filter>),
+    If(String, bool),
+    Else,
+    End,
+}
+
+impl Template {
+    pub fn parse(name: &str, source: &str) -> Result<Self, TemplateError> {
+        let mut tokens = Vec::new();
+        let mut text = String::new();
+        let mut chars = source.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut tag = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => tag.push(c),
+                            None => return Err(Self::syntax_error(name, "unclosed '{'")),
+                        }
+                    }
+                    if !text.is_empty() {
+                        tokens.push(Token::Text(std::mem::take(&mut text)));
+                    }
+                    tokens.push(Token::Tag(Self::parse_tag(name, &tag)?));
+                }
+                '}' => return Err(Self::syntax_error(name, "unmatched '}'")),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            tokens.push(Token::Text(text));
+        }
+
+        let mut tokens = tokens.into_iter();
+        let (nodes, closing) = Self::parse_nodes(name, &mut tokens)?;
+        match closing {
+            None => Ok(Self {
+                name: name.to_owned(),
+                nodes,
+            }),
+            Some(_) => Err(Self::syntax_error(name, "'else' or 'end' without 'if'")),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn placeholders(&self) -> Vec<String> {
+        let mut placeholders = Vec::new();
+        Self::collect_placeholders(&self.nodes, &mut placeholders);
+        placeholders
+    }
+
+    // Conditions hold when their placeholder is set to something other than "" or "false"
+    pub fn render(&self, context: &HashMap<&str, String>) -> String {
+        let mut output = String::new();
+        Self::render_nodes(&self.nodes, context, |value| value, &mut output);
+        output
+    }
+
+    // Placeholder values are escaped, the markup of the template is kept as is
+    pub fn render_html(&self, context: &HashMap<&str, String>) -> String {
+        let mut output = String::new();
+        Self::render_nodes(&self.nodes, context, escape_html, &mut output);
+        output
+    }
+
+    fn parse_tag(name: &str, tag: &str) -> Result<Tag, TemplateError> {
+        let words: Vec<&str> = tag.split_whitespace().collect();
+        match words.as_slice() {
+            ["if", placeholder] => Ok(Tag::If(Self::identifier(name, placeholder)?, false)),
+            ["if", "not", placeholder] => Ok(Tag::If(Self::identifier(name, placeholder)?, true)),
+            ["else"] => Ok(Tag::Else),
+            ["end"] => Ok(Tag::End),
+            [expression] => {
+                let mut parts = expression.split('|');
+                let placeholder = Self::identifier(name, parts.next().unwrap_or_default())?;
+                let filters = parts
+                    .map(|filter| match filter {
+                        "upper" => Ok(Filter::Upper),
+                        "lower" => Ok(Filter::Lower),
+                        "capitalize" => Ok(Filter::Capitalize),
+                        "ordinal" => Ok(Filter::Ordinal),
+                        _ => Err(TemplateError::UnknownFilter {
+                            template: name.to_owned(),
+                            filter: filter.to_owned(),
+                        }),
+                    })
+                    .collect::<Result<Vec<Filter>, TemplateError>>()?;
+                Ok(Tag::Placeholder(placeholder, filters))
+            }
+            _ => Err(Self::syntax_error(
+                name,
+                &format!("invalid tag '{{{}}}'", tag),
+            )),
+        }
+    }
+
+    fn identifier(name: &str, identifier: &str) -> Result<String, TemplateError> {
+        if identifier.is_empty()
+            || !identifier
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(Self::syntax_error(
+                name,
+                &format!("invalid placeholder '{}'", identifier),
+            ));
+        }
+        Ok(identifier.to_owned())
+    }
+
+    // Parses up to the closing "{else}" or "{end}" tag, if any
+    fn parse_nodes(
+        name: &str,
+        tokens: &mut impl Iterator<Item = Token>,
+    ) -> Result<(Vec<Node>, Option<Tag>), TemplateError> {
+        let mut nodes = Vec::new();
+        while let Some(token) = tokens.next() {
+            match token {
+                Token::Text(text) => nodes.push(Node::Text(text)),
+                Token::Tag(Tag::Placeholder(placeholder, filters)) => {
+                    nodes.push(Node::Placeholder {
+                        name: placeholder,
+                        filters,
+                    })
+                }
+                Token::Tag(Tag::If(placeholder, negated)) => {
+                    let (then, closing) = Self::parse_nodes(name, tokens)?;
+                    let otherwise = match closing {
+                        Some(Tag::Else) => match Self::parse_nodes(name, tokens)? {
+                            (otherwise, Some(Tag::End)) => otherwise,
+                            _ => return Err(Self::syntax_error(name, "missing 'end'")),
+                        },
+                        Some(Tag::End) => Vec::new(),
+                        _ => return Err(Self::syntax_error(name, "missing 'end'")),
+                    };
+                    nodes.push(Node::Conditional {
+                        name: placeholder,
+                        negated,
+                        then,
+                        otherwise,
+                    });
+                }
+                Token::Tag(closing) => return Ok((nodes, Some(closing))),
+            }
+        }
+        Ok((nodes, None))
+    }
+
+    fn collect_placeholders(nodes: &[Node], placeholders: &mut Vec<String>) {
+        for node in nodes {
+            let name = match node {
+                Node::Text(_) => continue,
+                Node::Placeholder { name, .. } => name,
+                Node::Conditional {
+                    name,
+                    then,
+                    otherwise,
+                    ..
+                } => {
+                    Self::collect_placeholders(then, placeholders);
+                    Self::collect_placeholders(otherwise, placeholders);
+                    name
+                }
+            };
+            if !placeholders.contains(name) {
+                placeholders.push(name.to_owned());
+            }
+        }
+    }
+
+    fn render_nodes(
+        nodes: &[Node],
+        context: &HashMap<&str, String>,
+        escape: fn(String) -> String,
+        output: &mut String,
+    ) {
+        for node in nodes {
+            match node {
+                Node::Text(text) => output.push_str(text),
+                Node::Placeholder { name, filters } => {
+                    let value = context.get(name.as_str()).cloned().unwrap_or_default();
+                    output.push_str(&escape(
+                        filters
+                            .iter()
+                            .fold(value, |value, filter| Self::apply(*filter, value)),
+                    ));
+                }
+                Node::Conditional {
+                    name,
+                    negated,
+                    then,
+                    otherwise,
+                } => {
+                    let value = context.get(name.as_str()).map(String::as_str);
+                    let holds = !matches!(value, None | Some("") | Some("false"));
+                    let branch = if holds != *negated { then } else { otherwise };
+                    Self::render_nodes(branch, context, escape, output);
+                }
+            }
+        }
+    }
+
+    fn apply(filter: Filter, value: String) -> String {
+        match filter {
+            Filter::Upper => value.to_uppercase(),
+            Filter::Lower => value.to_lowercase(),
+            Filter::Capitalize => {
+                let mut chars = value.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => value,
+                }
+            }
+            Filter::Ordinal => match value.parse::<u32>() {
+                Ok(number) => ordinal(number),
+                Err(_) => value,
+            },
+        }
+    }
+
+    fn syntax_error(name: &str, message: &str) -> TemplateError {
+        TemplateError::InvalidSyntax {
+            template: name.to_owned(),
+            message: message.to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateError {
+    Io(String),
+    InvalidSyntax {
+        template: String,
+        message: String,
+    },
+    UnknownFilter {
+        template: String,
+        filter: String,
+    },
+    UnknownPlaceholder {
+        template: String,
+        placeholder: String,
+    },
+    MissingTemplate(String),
+    InvalidLocale(String),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::Io(message) => write!(f, "unable to load templates: {}", message),
+            TemplateError::InvalidSyntax { template, message } => {
+                write!(f, "invalid template '{}': {}", template, message)
+            }
+            TemplateError::UnknownFilter { template, filter } => {
+                write!(f, "unknown filter '{}' in template '{}'", filter, template)
+            }
+            TemplateError::UnknownPlaceholder {
+                template,
+                placeholder,
+            } => write!(
+                f,
+                "unknown placeholder '{}' in template '{}'",
+                placeholder, template
+            ),
+            TemplateError::MissingTemplate(template) => {
+                write!(f, "missing template '{}'", template)
+            }
+            TemplateError::InvalidLocale(locale) => {
+                write!(f, "invalid template locale '{}'", locale)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+fn escape_html(value: String) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
@@ -0,0 +1,69 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::{friends::friend_data::Locale, greetings::greeting::Greeting};
+
+use super::{message_templates::MessageTemplates, template::TemplateError};
+
+// Translations missing some messages fall back along "it-CH" -> "it" -> default
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranslationCatalog {
+    default: MessageTemplates,
+    translations: HashMap<Locale, MessageTemplates>,
+}
+
+impl TranslationCatalog {
+    pub fn new(default: MessageTemplates) -> Self {
+        Self {
+            default,
+            translations: HashMap::new(),
+        }
+    }
+
+    pub fn with_translation(mut self, locale: Locale, templates: MessageTemplates) -> Self {
+        self.translations.insert(locale, templates);
+        self
+    }
+
+    // Templates of the directory are the default ones, those of its "it", "de-CH"... subdirectories their translations
+    pub fn load(directory: impl AsRef<Path>) -> Result<Self, TemplateError> {
+        let io_error = |e: std::io::Error| TemplateError::Io(e.to_string());
+        let mut catalog = Self::new(MessageTemplates::load(&directory)?);
+        for entry in fs::read_dir(&directory).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let locale = Locale::parse(&name)
+                .ok_or_else(|| TemplateError::InvalidLocale(name.to_string()))?;
+            catalog = catalog.with_translation(locale, MessageTemplates::load(&path)?);
+        }
+        Ok(catalog)
+    }
+
+    pub(crate) fn validate(&self, messages: &[&str]) -> Result<(), TemplateError> {
+        self.default.validate(messages)?;
+        self.translations
+            .values()
+            .try_for_each(MessageTemplates::validate_placeholders)
+    }
+
+    pub(crate) fn validate_one_of(&self, messages: &[&str]) -> Result<(), TemplateError> {
+        self.default.validate_one_of(messages)
+    }
+
+    // The locale is chosen once per greeting, so all its messages are in the same language
+    pub(crate) fn templates_for(
+        &self,
+        greeting: &Greeting,
+        has_all_messages: impl Fn(&MessageTemplates) -> bool,
+    ) -> &MessageTemplates {
+        greeting
+            .locale
+            .iter()
+            .flat_map(Locale::fallbacks)
+            .filter_map(|locale| self.translations.get(&locale))
+            .find(|templates| has_all_messages(templates))
+            .unwrap_or(&self.default)
+    }
+}
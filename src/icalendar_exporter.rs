@@ -0,0 +1,144 @@
+use std::{fmt::Display, io::Write, rc::Rc};
+
+use crate::{
+    friends::{
+        birthdate::LeapDayPolicy,
+        friend_data::FriendData,
+        friends_gateway::{FriendsGateway, FriendsGatewayError},
+    },
+    greeter_service::Calendar,
+};
+
+const MAX_LINE_OCTETS: usize = 75;
+const LEAP_YEAR: i32 = 2000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ICalendarExportError {
+    FriendsNotLoaded(FriendsGatewayError),
+    Io(String),
+}
+
+impl Display for ICalendarExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ICalendarExportError::FriendsNotLoaded(error) => error.fmt(f),
+            ICalendarExportError::Io(message) => write!(f, "unable to write calendar: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ICalendarExportError {}
+
+pub struct ICalendarExporter {
+    friends_gateway: Rc<dyn FriendsGateway>,
+    calendar: Rc<dyn Calendar>,
+    leap_day_policy: LeapDayPolicy,
+}
+
+impl ICalendarExporter {
+    pub fn new(
+        friends_gateway: Rc<impl FriendsGateway + 'static>,
+        calendar: Rc<impl Calendar + 'static>,
+    ) -> Self {
+        Self {
+            friends_gateway,
+            calendar,
+            leap_day_policy: LeapDayPolicy::February28,
+        }
+    }
+
+    pub fn configure_leap_day_policy(&mut self, leap_day_policy: LeapDayPolicy) {
+        self.leap_day_policy = leap_day_policy
+    }
+
+    pub fn export(&self, writer: &mut impl Write) -> Result<(), ICalendarExportError> {
+        let friends = self
+            .friends_gateway
+            .get_friends()
+            .map_err(ICalendarExportError::FriendsNotLoaded)?;
+        let timestamp = self.calendar.today().format("%Y%m%dT000000Z").to_string();
+
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_owned(),
+            "VERSION:2.0".to_owned(),
+            "PRODID:-//birthday-greetings-kata-rust//Birthdays//EN".to_owned(),
+            "CALSCALE:GREGORIAN".to_owned(),
+        ];
+        for friend in friends {
+            lines.extend(birthday_event(&friend, &timestamp, self.leap_day_policy));
+        }
+        lines.push("END:VCALENDAR".to_owned());
+
+        for line in lines {
+            writer
+                .write_all(fold(&line).as_bytes())
+                .map_err(|e| ICalendarExportError::Io(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+fn birthday_event(
+    friend: &FriendData,
+    timestamp: &str,
+    leap_day_policy: LeapDayPolicy,
+) -> Vec<String> {
+    let birthdate = friend.birthdate;
+    let start_year = birthdate.year().unwrap_or(LEAP_YEAR);
+    let recurrence_rule = if birthdate.month() == 2 && birthdate.day() == 29 {
+        // Feb 29 birthdays recur when the greeter service celebrates them in common years
+        match leap_day_policy {
+            LeapDayPolicy::February28 => "RRULE:FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=-1",
+            // The 60th day of the year is Feb 29 in leap years and Mar 1 otherwise
+            LeapDayPolicy::March1 => "RRULE:FREQ=YEARLY;BYYEARDAY=60",
+            LeapDayPolicy::Skip => "RRULE:FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=29",
+        }
+    } else {
+        "RRULE:FREQ=YEARLY"
+    };
+    vec![
+        "BEGIN:VEVENT".to_owned(),
+        format!("UID:{}", event_uid(friend)),
+        format!("DTSTAMP:{}", timestamp),
+        format!(
+            "DTSTART;VALUE=DATE:{:04}{:02}{:02}",
+            start_year,
+            birthdate.month(),
+            birthdate.day()
+        ),
+        recurrence_rule.to_owned(),
+        format!(
+            "SUMMARY:{}",
+            escape_text(&format!("{} {}'s birthday", friend.name, friend.surname))
+        ),
+        "TRANSP:TRANSPARENT".to_owned(),
+        "END:VEVENT".to_owned(),
+    ]
+}
+
+// The friend id keeps the event the same across exports, and apart from those of homonyms
+fn event_uid(friend: &FriendData) -> String {
+    format!("{}@birthday-greetings", escape_text(friend.id.as_str()))
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut line_octets = 0;
+    for c in line.chars() {
+        if line_octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            line_octets = 1;
+        }
+        folded.push(c);
+        line_octets += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
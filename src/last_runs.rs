@@ -0,0 +1,3 @@
+pub mod file_last_run_store;
+pub mod in_memory_last_run_store;
+pub mod last_run_store;
@@ -0,0 +1,87 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process,
+};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use super::last_run_store::{LastRunStore, LastRunStoreError};
+
+// The first line holds the last run time in RFC 3339, empty before the first successful run,
+// each following one a greeting sent since
+pub struct FileLastRunStore {
+    path: PathBuf,
+}
+
+impl FileLastRunStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_owned(),
+        }
+    }
+
+    fn read(&self) -> Result<(Option<DateTime<Utc>>, Vec<String>), LastRunStoreError> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok((None, Vec::new())),
+            Err(e) => return Err(LastRunStoreError::Io(e.to_string())),
+        };
+        let mut lines = content.lines();
+        let last_run = match lines.next().map(str::trim).unwrap_or_default() {
+            "" => None,
+            instant => Some(
+                DateTime::parse_from_rfc3339(instant)
+                    .map_err(|_| LastRunStoreError::InvalidDate(instant.to_owned()))?
+                    .with_timezone(&Utc),
+            ),
+        };
+        Ok((last_run, lines.map(str::to_owned).collect()))
+    }
+
+    fn write(
+        &self,
+        last_run: Option<DateTime<Utc>>,
+        sent_greetings: &[String],
+    ) -> Result<(), LastRunStoreError> {
+        let mut content = last_run
+            .map(|instant| instant.to_rfc3339_opts(SecondsFormat::Secs, true))
+            .unwrap_or_default();
+        content.push('\n');
+        for greeting in sent_greetings {
+            content.push_str(greeting);
+            content.push('\n');
+        }
+        let file_name = self.path.file_name().unwrap_or_default().to_string_lossy();
+        let temp_path = self
+            .path
+            .with_file_name(format!(".{}.{}.tmp", file_name, process::id()));
+        let written =
+            fs::write(&temp_path, content).and_then(|_| fs::rename(&temp_path, &self.path));
+        if let Err(e) = written {
+            let _ = fs::remove_file(&temp_path);
+            return Err(LastRunStoreError::Io(e.to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl LastRunStore for FileLastRunStore {
+    fn last_run(&self) -> Result<Option<DateTime<Utc>>, LastRunStoreError> {
+        self.read().map(|(last_run, _)| last_run)
+    }
+
+    fn record_run(&self, instant: DateTime<Utc>) -> Result<(), LastRunStoreError> {
+        self.write(Some(instant), &[])
+    }
+
+    fn sent_greetings(&self) -> Result<Vec<String>, LastRunStoreError> {
+        self.read().map(|(_, sent_greetings)| sent_greetings)
+    }
+
+    fn record_sent_greetings(&self, greetings: &[String]) -> Result<(), LastRunStoreError> {
+        let (last_run, _) = self.read()?;
+        self.write(last_run, greetings)
+    }
+}
@@ -0,0 +1,46 @@
+use std::cell::{Cell, RefCell};
+
+use chrono::{DateTime, Utc};
+
+use super::last_run_store::{LastRunStore, LastRunStoreError};
+
+pub struct InMemoryLastRunStore {
+    last_run: Cell<Option<DateTime<Utc>>>,
+    sent_greetings: RefCell<Vec<String>>,
+}
+
+impl InMemoryLastRunStore {
+    pub fn new() -> Self {
+        Self {
+            last_run: Cell::new(None),
+            sent_greetings: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl Default for InMemoryLastRunStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LastRunStore for InMemoryLastRunStore {
+    fn last_run(&self) -> Result<Option<DateTime<Utc>>, LastRunStoreError> {
+        Ok(self.last_run.get())
+    }
+
+    fn record_run(&self, instant: DateTime<Utc>) -> Result<(), LastRunStoreError> {
+        self.last_run.set(Some(instant));
+        self.sent_greetings.borrow_mut().clear();
+        Ok(())
+    }
+
+    fn sent_greetings(&self) -> Result<Vec<String>, LastRunStoreError> {
+        Ok(self.sent_greetings.borrow().clone())
+    }
+
+    fn record_sent_greetings(&self, greetings: &[String]) -> Result<(), LastRunStoreError> {
+        self.sent_greetings.replace(greetings.to_vec());
+        Ok(())
+    }
+}
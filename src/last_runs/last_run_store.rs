@@ -0,0 +1,32 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+
+// Besides the last successful run, stores the greetings already sent by the failed runs since,
+// so that catching up doesn't send them twice
+pub trait LastRunStore {
+    fn last_run(&self) -> Result<Option<DateTime<Utc>>, LastRunStoreError>;
+    // Also forgets the greetings sent since the previous run
+    fn record_run(&self, instant: DateTime<Utc>) -> Result<(), LastRunStoreError>;
+    fn sent_greetings(&self) -> Result<Vec<String>, LastRunStoreError>;
+    fn record_sent_greetings(&self, greetings: &[String]) -> Result<(), LastRunStoreError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LastRunStoreError {
+    Io(String),
+    InvalidDate(String),
+}
+
+impl Display for LastRunStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LastRunStoreError::Io(message) => write!(f, "unable to access last run: {}", message),
+            LastRunStoreError::InvalidDate(value) => {
+                write!(f, "invalid last run time '{}'", value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LastRunStoreError {}
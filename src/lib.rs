@@ -1,4 +1,7 @@
+pub mod calendars;
 pub mod friends;
 pub mod greeter_service;
 pub mod greetings;
+pub mod icalendar_exporter;
+pub mod last_runs;
 pub mod log_observer;
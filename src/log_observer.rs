@@ -1,6 +1,9 @@
-use log::info;
+use log::{info, warn};
 
-use crate::{friends::friend_data::FriendData, greeter_service::Observer};
+use crate::{
+    friends::{friend_data::FriendData, friends_gateway::FriendsLoadReport},
+    greeter_service::Observer,
+};
 
 pub struct LogObserver {}
 
@@ -20,10 +23,28 @@ impl Observer for LogObserver {
     fn observe_friends_celebrating_their_birthdays(&self, friends: Vec<FriendData>) {
         for friend in friends {
             info!(
-                "{} {} celebreting her birtday on {}",
+                "{} {} celebreting her birtday on {:02}/{:02}",
                 friend.name,
                 friend.surname,
-                friend.birthdate.format("%d/%m")
+                friend.birthdate.day(),
+                friend.birthdate.month()
+            );
+        }
+    }
+
+    fn observe_friends_load_report(&self, report: FriendsLoadReport) {
+        for rejected_record in report.rejected_records {
+            warn!(
+                "Skipped friend at line {} \"{}\" - {}",
+                rejected_record.line, rejected_record.content, rejected_record.reason
+            );
+        }
+        for resolved_conflict in report.resolved_conflicts {
+            info!(
+                "Merged {} duplicates of {} {}",
+                resolved_conflict.duplicates.len(),
+                resolved_conflict.resolved.name,
+                resolved_conflict.resolved.surname
             );
         }
     }
@@ -1,5 +1,9 @@
 use birthday_greetings_kata_rust::{
-    friends::flat_file_friends_gateway::FlatFileFriendsGateway,
+    calendars::fixed_calendar::FixedCalendar,
+    friends::{
+        flat_file_friends_gateway::FlatFileFriendsGateway, friends_gateway::FriendsGateway,
+        json_friends_gateway::JsonFriendsGateway, vcard_friends_gateway::VCardFriendsGateway,
+    },
     greeter_service::{Calendar, GreeterService},
     greetings::{
         emails::{email::Email, email_greetings_sender::EmailGreetingsSender, mailer::Mailer},
@@ -17,22 +21,6 @@ use std::rc::Rc;
 use std::{cell::RefCell, io::Result};
 use tempfile::NamedTempFile;
 
-struct FakeCalendar {
-    date: NaiveDate,
-}
-
-impl FakeCalendar {
-    fn new(date: NaiveDate) -> Self {
-        Self { date }
-    }
-}
-
-impl Calendar for FakeCalendar {
-    fn today(&self) -> NaiveDate {
-        self.date
-    }
-}
-
 struct MailerTestDouble {
     sent_emails: RefCell<Vec<Email>>,
 }
@@ -95,8 +83,9 @@ fn send_greetings_via_email_and_sms() -> Result<()> {
     )?;
     writeln!(temp_file, "Doe, Mary, 24/08/1982, mary@doe.com, 3396665559")?;
     let flat_file_friends_gateway = Rc::new(FlatFileFriendsGateway::new(temp_file.reopen()?));
-    let fake_calendar = FakeCalendar::new(NaiveDate::from_ymd_opt(2023, 8, 24).unwrap());
-    let calendar = Rc::new(fake_calendar);
+    let calendar = Rc::new(FixedCalendar::new(
+        NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(),
+    ));
     let mailer_test_double = Rc::new(MailerTestDouble::new());
     let email_greetings_sender = Rc::new(EmailGreetingsSender::new(Rc::clone(&mailer_test_double)));
     let sms_service_test_double = Rc::new(SmsServiceTestDouble::new());
@@ -115,7 +104,7 @@ fn send_greetings_via_email_and_sms() -> Result<()> {
     );
     let observer = Rc::new(LogObserver::default());
     greeter.configure_observer(Rc::clone(&observer));
-    greeter.run();
+    greeter.run().unwrap();
 
     let emails = mailer_test_double.spied_emails_to_send();
     let sms = sms_service_test_double.spied_sms_to_send();
@@ -164,3 +153,174 @@ fn send_greetings_via_email_and_sms() -> Result<()> {
     });
     Ok(())
 }
+
+#[test]
+fn remind_all_other_friends_of_todays_birthdays() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    writeln!(
+        temp_file,
+        "Franchi, Franco, 24/08/1970, franco@franchi.com, 3398889990"
+    )?;
+    writeln!(
+        temp_file,
+        "Germi, Mario, 11/12/1980, mario@germi.com, 3334442221"
+    )?;
+    writeln!(temp_file, "Doe, Mary, 24/08/1982, mary@doe.com, 3396665559")?;
+    let flat_file_friends_gateway = Rc::new(FlatFileFriendsGateway::new(temp_file.reopen()?));
+    let calendar = Rc::new(FixedCalendar::new(
+        NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(),
+    ));
+    let mailer_test_double = Rc::new(MailerTestDouble::new());
+    let email_greetings_sender = Rc::new(EmailGreetingsSender::new(Rc::clone(&mailer_test_double)));
+    let sms_service_test_double = Rc::new(SmsServiceTestDouble::new());
+    let sms_greetings_sender =
+        Rc::new(SmsGreetingsSender::new(Rc::clone(&sms_service_test_double)));
+    let senders: Vec<Rc<dyn GreetingsSender>> = vec![email_greetings_sender, sms_greetings_sender];
+    let greetings_sender_announcer = Rc::new(GreetingsSenderAnnouncer::new(senders));
+
+    let mut greeter = GreeterService::new(
+        Rc::clone(&flat_file_friends_gateway),
+        calendar,
+        Rc::clone(&greetings_sender_announcer),
+    );
+    greeter.configure_birthday_announcements();
+    greeter.run().unwrap();
+
+    let emails = mailer_test_double.spied_emails_to_send();
+    let sms = sms_service_test_double.spied_sms_to_send();
+    assert_eq!(
+        emails[2..],
+        vec![
+            Email::new(
+                "greeting@service.com",
+                "franco@franchi.com",
+                "Birthday reminder",
+                "Dear Franco,\nToday is Mary Doe's birthday. Don't forget to send them a message!"
+            ),
+            Email::new(
+                "greeting@service.com",
+                "mario@germi.com",
+                "Birthday reminder",
+                "Dear Mario,\nToday is Franco Franchi's and Mary Doe's birthday. Don't forget to send them a message!"
+            ),
+            Email::new(
+                "greeting@service.com",
+                "mary@doe.com",
+                "Birthday reminder",
+                "Dear Mary,\nToday is Franco Franchi's birthday. Don't forget to send them a message!"
+            )
+        ]
+    );
+    assert_eq!(
+        sms[2..],
+        vec![
+            Sms::new(
+                "3334445551",
+                "3398889990",
+                "Today is Mary Doe's birthday. Don't forget to send them a message!"
+            ),
+            Sms::new(
+                "3334445551",
+                "3334442221",
+                "Today is Franco Franchi's and Mary Doe's birthday. Don't forget to send them a message!"
+            ),
+            Sms::new(
+                "3334445551",
+                "3396665559",
+                "Today is Franco Franchi's birthday. Don't forget to send them a message!"
+            )
+        ]
+    );
+    Ok(())
+}
+
+struct DayByDayCalendar {
+    today: RefCell<NaiveDate>,
+}
+
+impl Calendar for DayByDayCalendar {
+    fn today(&self) -> NaiveDate {
+        *self.today.borrow()
+    }
+}
+
+fn greet_on_two_consecutive_days(friends_gateway: Rc<impl FriendsGateway + 'static>) -> Vec<Email> {
+    let calendar = Rc::new(DayByDayCalendar {
+        today: RefCell::new(NaiveDate::from_ymd_opt(2023, 8, 24).unwrap()),
+    });
+    let mailer_test_double = Rc::new(MailerTestDouble::new());
+    let greeter = GreeterService::new(
+        friends_gateway,
+        Rc::clone(&calendar),
+        Rc::new(EmailGreetingsSender::new(Rc::clone(&mailer_test_double))),
+    );
+
+    greeter.run().unwrap();
+    calendar
+        .today
+        .replace(NaiveDate::from_ymd_opt(2023, 8, 25).unwrap());
+    greeter.run().unwrap();
+
+    mailer_test_double.spied_emails_to_send()
+}
+
+#[test]
+fn load_friends_from_files_again_at_each_run() -> Result<()> {
+    let mut flat_file = NamedTempFile::new()?;
+    write!(
+        flat_file,
+        "last_name, first_name, date_of_birth, email, phone_number\n\
+         Franchi, Franco, 24/08/1970, franco@franchi.com, 3398889990\n\
+         Doe, Mary, 25/08/1982, mary@doe.com, 3396665559\n"
+    )?;
+    let mut json_file = NamedTempFile::new()?;
+    write!(
+        json_file,
+        r#"[
+            {{"name": "Franco", "surname": "Franchi", "birthdate": "24/08/1970",
+              "email": "franco@franchi.com", "phone_number": "3398889990"}},
+            {{"name": "Mary", "surname": "Doe", "birthdate": "25/08/1982",
+              "email": "mary@doe.com", "phone_number": "3396665559"}}
+        ]"#
+    )?;
+    let mut vcard_file = NamedTempFile::new()?;
+    write!(
+        vcard_file,
+        "BEGIN:VCARD\nVERSION:3.0\nN:Franchi;Franco;;;\nBDAY:1970-08-24\n\
+         EMAIL:franco@franchi.com\nTEL:3398889990\nEND:VCARD\n\
+         BEGIN:VCARD\nVERSION:3.0\nN:Doe;Mary;;;\nBDAY:1982-08-25\n\
+         EMAIL:mary@doe.com\nTEL:3396665559\nEND:VCARD\n"
+    )?;
+    let expected_emails = vec![
+        Email::new(
+            "greeting@service.com",
+            "franco@franchi.com",
+            "Happy birthday!",
+            "Happy birthday, dear Franco!",
+        ),
+        Email::new(
+            "greeting@service.com",
+            "mary@doe.com",
+            "Happy birthday!",
+            "Happy birthday, dear Mary!",
+        ),
+    ];
+
+    assert_eq!(
+        greet_on_two_consecutive_days(Rc::new(FlatFileFriendsGateway::new(flat_file.reopen()?))),
+        expected_emails
+    );
+    assert_eq!(
+        greet_on_two_consecutive_days(Rc::new(JsonFriendsGateway::new(json_file.reopen()?))),
+        expected_emails
+    );
+    assert_eq!(
+        greet_on_two_consecutive_days(Rc::new(VCardFriendsGateway::new(vcard_file.reopen()?))),
+        expected_emails
+    );
+    Ok(())
+}
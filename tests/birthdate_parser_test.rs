@@ -0,0 +1,170 @@
+use birthday_greetings_kata_rust::friends::birthdate::Birthdate;
+use birthday_greetings_kata_rust::friends::birthdate_parser::{
+    BirthdateParseError, BirthdateParser, DateFormat, ParsedBirthdate,
+};
+
+#[test]
+fn parse_birthdates_in_european_format_by_default() {
+    let birthdate_parser = BirthdateParser::default();
+
+    let parsed_birthdate = birthdate_parser.parse("24/08/1970");
+
+    assert_eq!(
+        parsed_birthdate,
+        Ok(ParsedBirthdate {
+            birthdate: Birthdate::from_ymd_opt(1970, 8, 24).unwrap(),
+            format: DateFormat::european()
+        })
+    )
+}
+
+#[test]
+fn report_the_format_matching_the_birthdate() {
+    let birthdate_parser = BirthdateParser::new(vec![
+        DateFormat::iso(),
+        DateFormat::us(),
+        DateFormat::dotted(),
+    ]);
+
+    assert_eq!(
+        birthdate_parser.parse("1970-08-24").map(|p| p.format),
+        Ok(DateFormat::iso())
+    );
+    assert_eq!(
+        birthdate_parser.parse("08/24/1970").map(|p| p.format),
+        Ok(DateFormat::us())
+    );
+    assert_eq!(
+        birthdate_parser.parse("24.08.1970").map(|p| p.format),
+        Ok(DateFormat::dotted())
+    );
+}
+
+#[test]
+fn fail_to_parse_birthdates_matching_no_format() {
+    let birthdate_parser = BirthdateParser::new(vec![DateFormat::iso(), DateFormat::dotted()]);
+
+    let parsed_birthdate = birthdate_parser.parse("24/08/1970");
+
+    assert_eq!(parsed_birthdate, Err(BirthdateParseError::Unrecognized))
+}
+
+#[test]
+fn fail_to_parse_birthdates_matching_formats_with_different_dates() {
+    let birthdate_parser = BirthdateParser::new(vec![DateFormat::european(), DateFormat::us()]);
+
+    let parsed_birthdate = birthdate_parser.parse("03/04/1990");
+
+    assert_eq!(
+        parsed_birthdate,
+        Err(BirthdateParseError::Ambiguous(vec![
+            DateFormat::european(),
+            DateFormat::us()
+        ]))
+    )
+}
+
+#[test]
+fn parse_birthdates_matching_formats_with_the_same_date() {
+    let birthdate_parser = BirthdateParser::new(vec![DateFormat::european(), DateFormat::us()]);
+
+    let parsed_birthdate = birthdate_parser.parse("04/04/1990");
+
+    assert_eq!(
+        parsed_birthdate,
+        Ok(ParsedBirthdate {
+            birthdate: Birthdate::from_ymd_opt(1990, 4, 4).unwrap(),
+            format: DateFormat::european()
+        })
+    )
+}
+
+#[test]
+fn resolve_ambiguous_birthdates_with_the_pinned_format() {
+    let mut birthdate_parser = BirthdateParser::new(vec![DateFormat::european(), DateFormat::us()]);
+    birthdate_parser.configure_pinned_format(DateFormat::us());
+
+    assert_eq!(
+        birthdate_parser.parse("03/04/1990"),
+        Ok(ParsedBirthdate {
+            birthdate: Birthdate::from_ymd_opt(1990, 3, 4).unwrap(),
+            format: DateFormat::us()
+        })
+    );
+    assert_eq!(
+        birthdate_parser.parse("24/08/1970"),
+        Ok(ParsedBirthdate {
+            birthdate: Birthdate::from_ymd_opt(1970, 8, 24).unwrap(),
+            format: DateFormat::european()
+        })
+    );
+}
+
+#[test]
+fn parse_birthdates_without_year_by_default() {
+    let birthdate_parser = BirthdateParser::default();
+
+    assert_eq!(
+        birthdate_parser.parse("24/08"),
+        Ok(ParsedBirthdate {
+            birthdate: Birthdate::from_md_opt(8, 24).unwrap(),
+            format: DateFormat::day_month()
+        })
+    );
+    assert_eq!(
+        birthdate_parser.parse("--08-24"),
+        Ok(ParsedBirthdate {
+            birthdate: Birthdate::from_md_opt(8, 24).unwrap(),
+            format: DateFormat::iso_month_day()
+        })
+    );
+}
+
+#[test]
+fn parse_february_29th_birthdates_without_year() {
+    let birthdate_parser = BirthdateParser::default();
+
+    let parsed_birthdate = birthdate_parser.parse("29/02");
+
+    assert_eq!(
+        parsed_birthdate.map(|p| p.birthdate),
+        Ok(Birthdate::from_md_opt(2, 29).unwrap())
+    )
+}
+
+#[test]
+fn fail_to_parse_non_existing_birthdates_without_year() {
+    let birthdate_parser = BirthdateParser::default();
+
+    let parsed_birthdate = birthdate_parser.parse("30/02");
+
+    assert_eq!(parsed_birthdate, Err(BirthdateParseError::Unrecognized))
+}
+
+#[test]
+fn format_birthdates_with_the_first_format_fitting_them() {
+    let birthdate_parser = BirthdateParser::default();
+
+    assert_eq!(
+        birthdate_parser.format(&Birthdate::from_ymd_opt(1970, 8, 24).unwrap()),
+        "24/08/1970"
+    );
+    assert_eq!(
+        birthdate_parser.format(&Birthdate::from_md_opt(2, 29).unwrap()),
+        "29/02"
+    );
+}
+
+#[test]
+fn format_birthdates_that_would_read_back_ambiguous_in_iso_format() {
+    let birthdate_parser = BirthdateParser::new(vec![DateFormat::european(), DateFormat::us()]);
+
+    assert_eq!(
+        birthdate_parser.format(&Birthdate::from_ymd_opt(1980, 4, 5).unwrap()),
+        "1980-04-05"
+    );
+    assert_eq!(
+        birthdate_parser.format(&Birthdate::from_ymd_opt(1980, 4, 24).unwrap()),
+        "24/04/1980"
+    );
+}
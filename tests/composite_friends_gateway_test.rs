@@ -0,0 +1,289 @@
+use birthday_greetings_kata_rust::friends::birthdate::Birthdate;
+use birthday_greetings_kata_rust::friends::composite_friends_gateway::{
+    CompositeFriendsGateway, ConflictResolutionPolicy,
+};
+use birthday_greetings_kata_rust::friends::flat_file_friends_gateway::FlatFileFriendsGateway;
+use birthday_greetings_kata_rust::friends::friend_data::{FriendData, FriendId};
+use birthday_greetings_kata_rust::friends::friends_gateway::{
+    FriendsGateway, FriendsGatewayError, FriendsLoadReport, InvalidFriendRecord, RejectedRecord,
+    ResolvedConflict,
+};
+use birthday_greetings_kata_rust::friends::json_friends_gateway::JsonFriendsGateway;
+use chrono::NaiveDate;
+use std::{
+    fs::{self, File},
+    rc::Rc,
+    time::{Duration, SystemTime},
+};
+use tempfile::tempdir;
+
+#[test]
+fn merge_friends_of_all_sources() {
+    let franca = franca();
+    let mario = mario();
+    let composite_friends_gateway = CompositeFriendsGateway::new(vec![
+        source(vec![franca.clone()], None),
+        source(vec![mario.clone()], None),
+    ]);
+
+    let (friends, report) = composite_friends_gateway.get_friends_with_report().unwrap();
+
+    assert_eq!(friends, vec![franca, mario]);
+    assert_eq!(report, FriendsLoadReport::default());
+}
+
+#[test]
+fn first_source_wins_by_default() {
+    let franca = franca();
+    let mut updated_franca = franca.clone();
+    updated_franca.phone_number = "3391112223".to_owned();
+    let composite_friends_gateway = CompositeFriendsGateway::new(vec![
+        source(vec![franca.clone(), mario()], None),
+        source(vec![updated_franca.clone()], None),
+    ]);
+
+    let (friends, report) = composite_friends_gateway.get_friends_with_report().unwrap();
+
+    assert_eq!(friends, vec![franca.clone(), mario()]);
+    assert_eq!(
+        report.resolved_conflicts,
+        vec![ResolvedConflict::new(
+            franca.clone(),
+            vec![franca, updated_franca]
+        )]
+    );
+}
+
+#[test]
+fn detect_duplicates_by_email_phone_number_or_name_and_birthdate() {
+    let franca = franca();
+    let same_email = FriendData::new(
+        "Francesca",
+        "Franchi",
+        NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+        "FRANCA@franchi.com",
+        "",
+    );
+    let same_phone_number = FriendData::new(
+        "Franca",
+        "Franchi-Rossi",
+        NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+        "franca@rossi.com",
+        "339 888 9990",
+    );
+    let same_name_and_birthdate = FriendData::new(
+        "franca",
+        "franchi",
+        NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+        "",
+        "",
+    );
+    let composite_friends_gateway = CompositeFriendsGateway::new(vec![
+        source(vec![franca.clone()], None),
+        source(
+            vec![same_email, same_phone_number, same_name_and_birthdate],
+            None,
+        ),
+    ]);
+
+    let (friends, report) = composite_friends_gateway.get_friends_with_report().unwrap();
+
+    assert_eq!(friends, vec![franca]);
+    assert_eq!(report.resolved_conflicts[0].duplicates.len(), 4);
+}
+
+#[test]
+fn most_recently_modified_source_wins() {
+    let franca = franca();
+    let mut updated_franca = franca.clone();
+    updated_franca.phone_number = "3391112223".to_owned();
+    let mut composite_friends_gateway = CompositeFriendsGateway::new(vec![
+        source(
+            vec![updated_franca.clone()],
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(200)),
+        ),
+        source(
+            vec![franca],
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(100)),
+        ),
+        source(vec![mario()], None),
+    ]);
+    composite_friends_gateway
+        .configure_conflict_resolution_policy(ConflictResolutionPolicy::MostRecentWins);
+
+    let friends = composite_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(friends, vec![updated_franca, mario()]);
+}
+
+#[test]
+fn most_recent_wins_between_file_sources_by_their_modification_time() {
+    let directory = tempdir().unwrap();
+    let flat_file_path = directory.path().join("friends.csv");
+    fs::write(
+        &flat_file_path,
+        "last_name, first_name, date_of_birth, email, phone_number\n\
+         Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990\n",
+    )
+    .unwrap();
+    File::options()
+        .write(true)
+        .open(&flat_file_path)
+        .unwrap()
+        .set_modified(SystemTime::now() - Duration::from_secs(3600))
+        .unwrap();
+    let json_file_path = directory.path().join("friends.json");
+    fs::write(
+        &json_file_path,
+        r#"[{"name": "Franca", "surname": "Franchi", "birthdate": "24/08/1970",
+             "email": "franca@franchi.com", "phone_number": "3391112223"}]"#,
+    )
+    .unwrap();
+    let mut composite_friends_gateway = CompositeFriendsGateway::new(vec![
+        Rc::new(FlatFileFriendsGateway::open(&flat_file_path)),
+        Rc::new(JsonFriendsGateway::new(
+            File::open(&json_file_path).unwrap(),
+        )),
+    ]);
+    composite_friends_gateway
+        .configure_conflict_resolution_policy(ConflictResolutionPolicy::MostRecentWins);
+
+    let friends = composite_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends
+            .iter()
+            .map(|friend| friend.phone_number.as_str())
+            .collect::<Vec<_>>(),
+        vec!["3391112223"]
+    );
+}
+
+#[test]
+fn merge_fields_of_duplicates() {
+    let without_phone_number = FriendData::new(
+        "Franca",
+        "Franchi",
+        Birthdate::from_md_opt(8, 24).unwrap(),
+        "franca@franchi.com",
+        "",
+    );
+    let without_email = FriendData::new(
+        "Franca",
+        "Franchi",
+        NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+        "",
+        "3398889990",
+    );
+    let mut composite_friends_gateway = CompositeFriendsGateway::new(vec![
+        source(vec![without_phone_number], None),
+        source(vec![without_email], None),
+    ]);
+    composite_friends_gateway
+        .configure_conflict_resolution_policy(ConflictResolutionPolicy::MergeFields);
+
+    let friends = composite_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends,
+        vec![franca().with_id(FriendId::new("franchi-franca-0824-franca@franchi.com"))]
+    );
+}
+
+#[test]
+fn collect_rejected_records_of_all_sources() {
+    let rejected_record = RejectedRecord::new(
+        3,
+        "Germi, Mario, 11/13/1980, mario@germi.com, 3396665559",
+        InvalidFriendRecord::InvalidDate("11/13/1980".to_owned()),
+    );
+    let composite_friends_gateway = CompositeFriendsGateway::new(vec![
+        Rc::new(FriendsGatewayStub {
+            friends: vec![franca()],
+            rejected_records: vec![rejected_record.clone()],
+            last_modified: None,
+        }),
+        source(vec![mario()], None),
+    ]);
+
+    let (friends, report) = composite_friends_gateway.get_friends_with_report().unwrap();
+
+    assert_eq!(friends, vec![franca(), mario()]);
+    assert_eq!(report.rejected_records, vec![rejected_record]);
+}
+
+#[test]
+fn fail_when_a_source_fails() {
+    let composite_friends_gateway = CompositeFriendsGateway::new(vec![
+        source(vec![franca()], None),
+        Rc::new(FailingFriendsGateway {}),
+    ]);
+
+    let result = composite_friends_gateway.get_friends();
+
+    assert_eq!(
+        result,
+        Err(FriendsGatewayError::Io("connection refused".to_owned()))
+    );
+}
+
+fn franca() -> FriendData {
+    FriendData::new(
+        "Franca",
+        "Franchi",
+        NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+        "franca@franchi.com",
+        "3398889990",
+    )
+}
+
+fn mario() -> FriendData {
+    FriendData::new(
+        "Mario",
+        "Germi",
+        NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+        "mario@germi.com",
+        "3396665559",
+    )
+}
+
+fn source(friends: Vec<FriendData>, last_modified: Option<SystemTime>) -> Rc<dyn FriendsGateway> {
+    Rc::new(FriendsGatewayStub {
+        friends,
+        rejected_records: Vec::new(),
+        last_modified,
+    })
+}
+
+struct FriendsGatewayStub {
+    friends: Vec<FriendData>,
+    rejected_records: Vec<RejectedRecord>,
+    last_modified: Option<SystemTime>,
+}
+
+impl FriendsGateway for FriendsGatewayStub {
+    fn get_friends(&self) -> Result<Vec<FriendData>, FriendsGatewayError> {
+        Ok(self.friends.clone())
+    }
+
+    fn get_friends_with_report(
+        &self,
+    ) -> Result<(Vec<FriendData>, FriendsLoadReport), FriendsGatewayError> {
+        Ok((
+            self.friends.clone(),
+            FriendsLoadReport::new(self.rejected_records.clone()),
+        ))
+    }
+
+    fn last_modified(&self) -> Option<SystemTime> {
+        self.last_modified
+    }
+}
+
+struct FailingFriendsGateway {}
+
+impl FriendsGateway for FailingFriendsGateway {
+    fn get_friends(&self) -> Result<Vec<FriendData>, FriendsGatewayError> {
+        Err(FriendsGatewayError::Io("connection refused".to_owned()))
+    }
+}
@@ -1,10 +1,17 @@
 use std::{cell::RefCell, rc::Rc};
 
+use birthday_greetings_kata_rust::friends::friend_data::{FriendId, Locale};
 use birthday_greetings_kata_rust::greetings::{
-    emails::{email::Email, email_greetings_sender::EmailGreetingsSender, mailer::Mailer},
-    greeting::Greeting,
+    emails::{
+        email::Email, email_greetings_sender::EmailGreetingsSender, inline_image::InlineImage,
+        mailer::Mailer,
+    },
+    greeting::{Greeting, GreetingKind, Milestone, Reminder},
     greetings_sender::GreetingsSender,
+    sender_identity::EmailIdentity,
+    templates::message_templates::MessageTemplates,
 };
+use chrono::NaiveDate;
 
 struct MailerTestDouble {
     sent_emails: RefCell<Vec<Email>>,
@@ -34,8 +41,20 @@ fn send_greetings_as_email() {
     let email_greetings_sender = EmailGreetingsSender::new(Rc::clone(&mailer_test_double));
 
     let greetings = vec![
-        Greeting::new("Franco", "Franchi", "franco@franchi.com", "3334445550"),
-        Greeting::new("Mary", "Doe", "mary@doe.com", "3336667770"),
+        Greeting::new(
+            &FriendId::new("franchi-franco"),
+            "Franco",
+            "Franchi",
+            "franco@franchi.com",
+            "3334445550",
+        ),
+        Greeting::new(
+            &FriendId::new("doe-mary"),
+            "Mary",
+            "Doe",
+            "mary@doe.com",
+            "3336667770",
+        ),
     ];
     let _ = email_greetings_sender.send(greetings);
 
@@ -59,6 +78,259 @@ fn send_greetings_as_email() {
     )
 }
 
+#[test]
+fn send_belated_greetings_as_email() {
+    let mailer_test_double = Rc::new(MailerTestDouble::new());
+    let email_greetings_sender = EmailGreetingsSender::new(Rc::clone(&mailer_test_double));
+
+    let greetings = vec![Greeting::new(
+        &FriendId::new("franchi-franco"),
+        "Franco",
+        "Franchi",
+        "franco@franchi.com",
+        "3334445550",
+    )
+    .with_kind(GreetingKind::BelatedBirthday)];
+    let _ = email_greetings_sender.send(greetings);
+
+    let emails = mailer_test_double.spied_emails_to_send();
+    assert_eq!(
+        emails,
+        vec![Email::new(
+            "greeting@service.com",
+            "franco@franchi.com",
+            "Happy belated birthday!",
+            "Happy belated birthday, dear Franco!"
+        )]
+    )
+}
+
+#[test]
+fn send_reminders_as_email() {
+    let mailer_test_double = Rc::new(MailerTestDouble::new());
+    let email_greetings_sender = EmailGreetingsSender::new(Rc::clone(&mailer_test_double));
+
+    let greetings = vec![Greeting::new(
+        &FriendId::new("doe-mary"),
+        "Mary",
+        "Doe",
+        "mary@doe.com",
+        "3336667770",
+    )
+    .with_kind(GreetingKind::Reminder(
+        Reminder::new(NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(), 3)
+            .with_friend("Franco", "Franchi"),
+    ))];
+    let _ = email_greetings_sender.send(greetings);
+
+    let emails = mailer_test_double.spied_emails_to_send();
+    assert_eq!(
+        emails,
+        vec![Email::new(
+            "greeting@service.com",
+            "mary@doe.com",
+            "Reminder: Franco Franchi's birthday",
+            "Dear Mary, Franco Franchi celebrates their birthday on 24/08. Don't forget to send them a message!"
+        )]
+    )
+}
+
+#[test]
+fn send_milestone_greetings_and_notices_as_email() {
+    let mailer_test_double = Rc::new(MailerTestDouble::new());
+    let email_greetings_sender = EmailGreetingsSender::new(Rc::clone(&mailer_test_double));
+
+    let greetings = vec![
+        Greeting::new(
+            &FriendId::new("franchi-franco"),
+            "Franco",
+            "Franchi",
+            "franco@franchi.com",
+            "3334445550",
+        )
+        .with_age(41)
+        .with_milestone(true),
+        Greeting::new(
+            &FriendId::new("doe-mary"),
+            "Mary",
+            "Doe",
+            "mary@doe.com",
+            "3336667770",
+        )
+        .with_kind(GreetingKind::Milestone(Milestone::new(
+            "Franco", "Franchi", 41,
+        ))),
+    ];
+    let _ = email_greetings_sender.send(greetings);
+
+    let emails = mailer_test_double.spied_emails_to_send();
+    assert_eq!(
+        emails,
+        vec![
+            Email::new(
+                "greeting@service.com",
+                "franco@franchi.com",
+                "Happy 41st birthday!",
+                "Happy 41st birthday, dear Franco!"
+            ),
+            Email::new(
+                "greeting@service.com",
+                "mary@doe.com",
+                "Franco Franchi turns 41 today",
+                "Dear Mary,\nToday Franco Franchi turns 41. Don't forget to send them a message!"
+            )
+        ]
+    )
+}
+
+#[test]
+fn send_greetings_in_the_language_of_each_friend() {
+    let mailer_test_double = Rc::new(MailerTestDouble::new());
+    let email_greetings_sender = EmailGreetingsSender::new(Rc::clone(&mailer_test_double));
+
+    let greetings = vec![
+        Greeting::new(
+            &FriendId::new("franchi-franco"),
+            "Franco",
+            "Franchi",
+            "franco@franchi.com",
+            "3334445550",
+        )
+        .with_age(50)
+        .with_milestone(true)
+        .with_locale(Locale::parse("it-CH")),
+        Greeting::new(
+            &FriendId::new("doe-mary"),
+            "Mary",
+            "Doe",
+            "mary@doe.com",
+            "3336667770",
+        )
+        .with_kind(GreetingKind::Reminder(
+            Reminder::new(NaiveDate::from_ymd_opt(2023, 3, 24).unwrap(), 3)
+                .with_friend("Franco", "Franchi")
+                .with_friend("Hans", "Meier"),
+        ))
+        .with_locale(Locale::parse("de")),
+    ];
+    let _ = email_greetings_sender.send(greetings);
+
+    let emails = mailer_test_double.spied_emails_to_send();
+    assert_eq!(
+        emails,
+        vec![
+            Email::new(
+                "greeting@service.com",
+                "franco@franchi.com",
+                "Buon 50° compleanno!",
+                "Buon 50° compleanno, Franco!"
+            ),
+            Email::new(
+                "greeting@service.com",
+                "mary@doe.com",
+                "Erinnerung: Geburtstag von Franco Franchi und Hans Meier",
+                "Hallo Mary, Franco Franchi und Hans Meier haben am 24. März Geburtstag. Vergiss nicht, eine Nachricht zu schicken!"
+            )
+        ]
+    )
+}
+
+#[test]
+fn send_greetings_from_the_configured_identity() {
+    let mailer_test_double = Rc::new(MailerTestDouble::new());
+    let mut email_greetings_sender = EmailGreetingsSender::new(Rc::clone(&mailer_test_double));
+    email_greetings_sender.configure_identity(
+        EmailIdentity::new("birthdays@example.org")
+            .and_then(|identity| identity.with_display_name("Example Birthdays"))
+            .and_then(|identity| identity.with_reply_to("friends@example.org"))
+            .unwrap(),
+    );
+
+    let _ = email_greetings_sender.send(vec![Greeting::new(
+        &FriendId::new("doe-mary"),
+        "Mary",
+        "Doe",
+        "mary@doe.com",
+        "3396665559",
+    )]);
+
+    let emails = mailer_test_double.spied_emails_to_send();
+    assert_eq!(
+        emails,
+        vec![Email::new(
+            "birthdays@example.org",
+            "mary@doe.com",
+            "Happy birthday!",
+            "Happy birthday, dear Mary!"
+        )
+        .with_from_name("Example Birthdays")
+        .with_reply_to("friends@example.org")]
+    )
+}
+
+#[test]
+fn send_html_greetings_with_an_inline_birthday_card() {
+    let mailer_test_double = Rc::new(MailerTestDouble::new());
+    let mut email_greetings_sender = EmailGreetingsSender::new(Rc::clone(&mailer_test_double));
+    email_greetings_sender
+        .configure_templates(
+            MessageTemplates::from_sources(&[
+                ("subject", "Happy birthday!"),
+                ("reminder_subject", "Reminder"),
+                ("milestone_subject", "Milestone"),
+                (
+                    "html",
+                    "<p>Happy birthday, dear {name}!</p><img src=\"cid:birthday-card\" alt=\"Cake\">",
+                ),
+                ("reminder_html", "<p>Don't forget {friends}!</p>"),
+                ("milestone_html", "<p>{friend_name} turns {age}</p>"),
+            ])
+            .unwrap(),
+        )
+        .unwrap();
+    let birthday_card = InlineImage::new("birthday-card", "image/png", vec![1, 2, 3]).unwrap();
+    email_greetings_sender.configure_inline_images(vec![birthday_card.clone()]);
+
+    let _ = email_greetings_sender.send(vec![
+        Greeting::new(
+            &FriendId::new("doe-mary"),
+            "Mary <Ann>",
+            "Doe",
+            "mary@doe.com",
+            "3396665559",
+        ),
+        Greeting::new(
+            &FriendId::new("franchi-franco"),
+            "Franco",
+            "Franchi",
+            "franco@franchi.com",
+            "3398889990",
+        )
+        .with_kind(GreetingKind::Reminder(
+            Reminder::new(NaiveDate::from_ymd_opt(2023, 3, 24).unwrap(), 0)
+                .with_friend("Mary", "Doe"),
+        )),
+    ]);
+
+    let emails = mailer_test_double.spied_emails_to_send();
+    assert_eq!(
+        emails,
+        vec![
+            Email::new("greeting@service.com", "mary@doe.com", "Happy birthday!", "")
+                .with_html_body(
+                    "<p>Happy birthday, dear Mary &lt;Ann&gt;!</p><img src=\"cid:birthday-card\" alt=\"Cake\">"
+                )
+                .with_inline_image(birthday_card),
+            Email::new("greeting@service.com", "franco@franchi.com", "Reminder", "")
+                .with_html_body("<p>Don't forget Mary Doe!</p>")
+        ]
+    );
+    assert_eq!(
+        emails[0].text_body,
+        "Happy birthday, dear Mary <Ann>!\n\nCake"
+    );
+}
+
 #[test]
 fn does_not_send_anything_when_asked_to_send_no_greeting() {
     let mailer_test_double = Rc::new(MailerTestDouble::new());
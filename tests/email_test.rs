@@ -0,0 +1,71 @@
+use birthday_greetings_kata_rust::greetings::emails::{
+    email::Email,
+    inline_image::{InlineImage, InlineImageError},
+    mime_part::MimePart,
+};
+
+fn birthday_card() -> InlineImage {
+    InlineImage::new("birthday-card", "image/png", vec![0x89, b'P', b'N', b'G']).unwrap()
+}
+
+#[test]
+fn structure_the_body_by_the_parts_of_the_email() {
+    let text_email = Email::new("a@b.com", "c@d.com", "Hi", "Happy birthday!");
+    let html_email = text_email
+        .clone()
+        .with_html_body("<p>Happy <b>birthday</b>!</p>");
+    let card_email = html_email.clone().with_inline_image(birthday_card());
+
+    assert_eq!(
+        text_email.body(),
+        MimePart::Text("Happy birthday!".to_owned())
+    );
+    assert_eq!(
+        html_email.body(),
+        MimePart::Alternative(vec![
+            MimePart::Text("Happy birthday!".to_owned()),
+            MimePart::Html("<p>Happy <b>birthday</b>!</p>".to_owned())
+        ])
+    );
+    assert_eq!(
+        card_email.body(),
+        MimePart::Alternative(vec![
+            MimePart::Text("Happy birthday!".to_owned()),
+            MimePart::Related(vec![
+                MimePart::Html("<p>Happy <b>birthday</b>!</p>".to_owned()),
+                MimePart::Image(birthday_card())
+            ])
+        ])
+    );
+    assert_eq!(card_email.body().content_type(), "multipart/alternative");
+}
+
+#[test]
+fn derive_a_missing_text_body_from_the_html() {
+    let email = Email::new("a@b.com", "c@d.com", "Hi", "")
+        .with_html_body("<h1>Happy birthday</h1><p>dear Mary!</p>");
+
+    assert_eq!(email.text_body, "Happy birthday\n\ndear Mary!");
+}
+
+#[test]
+fn reject_inline_images_that_cannot_be_referenced_or_shown() {
+    assert_eq!(
+        InlineImage::new("birthday card", "image/png", Vec::new()),
+        Err(InlineImageError::InvalidContentId(
+            "birthday card".to_owned()
+        ))
+    );
+    assert_eq!(
+        InlineImage::new("birthday-card", "text/html", Vec::new()),
+        Err(InlineImageError::UnsupportedContentType(
+            "text/html".to_owned()
+        ))
+    );
+    assert_eq!(
+        InlineImage::load("birthday-card", "card.bmp"),
+        Err(InlineImageError::UnsupportedContentType(
+            "card.bmp".to_owned()
+        ))
+    );
+}
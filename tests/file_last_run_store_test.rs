@@ -0,0 +1,59 @@
+use birthday_greetings_kata_rust::last_runs::{
+    file_last_run_store::FileLastRunStore,
+    last_run_store::{LastRunStore, LastRunStoreError},
+};
+use chrono::{TimeZone, Utc};
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn there_is_no_last_run_until_one_is_recorded() {
+    let dir = tempdir().unwrap();
+    let last_run_store = FileLastRunStore::new(dir.path().join("last_run"));
+
+    assert_eq!(last_run_store.last_run(), Ok(None));
+}
+
+#[test]
+fn record_and_read_back_the_last_run() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("last_run");
+    let last_run = Utc.with_ymd_and_hms(2023, 8, 24, 3, 0, 0).unwrap();
+
+    FileLastRunStore::new(&path).record_run(last_run).unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "2023-08-24T03:00:00Z\n");
+    assert_eq!(FileLastRunStore::new(&path).last_run(), Ok(Some(last_run)));
+}
+
+#[test]
+fn fail_reading_an_invalid_last_run() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("last_run");
+    fs::write(&path, "yesterday\n").unwrap();
+
+    assert_eq!(
+        FileLastRunStore::new(&path).last_run(),
+        Err(LastRunStoreError::InvalidDate("yesterday".to_owned()))
+    );
+}
+
+#[test]
+fn keep_the_greetings_sent_since_the_last_run_until_the_next_one() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("last_run");
+    let last_run_store = FileLastRunStore::new(&path);
+    let sent_greetings = vec!["birthday to 1".to_owned(), "birthday to 2".to_owned()];
+
+    last_run_store
+        .record_sent_greetings(&sent_greetings)
+        .unwrap();
+
+    assert_eq!(last_run_store.last_run(), Ok(None));
+    assert_eq!(last_run_store.sent_greetings(), Ok(sent_greetings));
+    last_run_store
+        .record_run(Utc.with_ymd_and_hms(2023, 8, 24, 0, 0, 0).unwrap())
+        .unwrap();
+    assert_eq!(last_run_store.sent_greetings(), Ok(Vec::new()));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "2023-08-24T00:00:00Z\n");
+}
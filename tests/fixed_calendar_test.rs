@@ -0,0 +1,38 @@
+use birthday_greetings_kata_rust::{
+    calendars::fixed_calendar::FixedCalendar, greeter_service::Calendar,
+};
+use chrono::{NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+
+#[test]
+fn always_return_the_given_date() {
+    let fixed_calendar = FixedCalendar::new(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+
+    assert_eq!(
+        fixed_calendar.today(),
+        NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+    );
+    assert_eq!(
+        fixed_calendar.today(),
+        NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+    );
+}
+
+#[test]
+fn return_the_given_instant_and_its_date_in_its_time_zone() {
+    let now = Tz::Asia__Tokyo
+        .with_ymd_and_hms(2024, 3, 1, 8, 0, 0)
+        .unwrap();
+
+    let fixed_calendar = FixedCalendar::at(now);
+
+    assert_eq!(
+        fixed_calendar.today(),
+        NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()
+    );
+    assert_eq!(
+        fixed_calendar.now(),
+        Utc.with_ymd_and_hms(2024, 2, 29, 23, 0, 0).unwrap()
+    );
+    assert_eq!(fixed_calendar.time_zone(), Tz::Asia__Tokyo);
+}
@@ -1,10 +1,19 @@
-use birthday_greetings_kata_rust::friends::flat_file_friends_gateway::FlatFileFriendsGateway;
-use birthday_greetings_kata_rust::friends::friend_data::FriendData;
-use birthday_greetings_kata_rust::friends::friends_gateway::FriendsGateway;
+use birthday_greetings_kata_rust::friends::birthdate::Birthdate;
+use birthday_greetings_kata_rust::friends::birthdate_parser::{BirthdateParser, DateFormat};
+use birthday_greetings_kata_rust::friends::flat_file_friends_gateway::{
+    FlatFileFriendsGateway, FriendColumn, LoadingMode,
+};
+use birthday_greetings_kata_rust::friends::friend_data::{FriendData, FriendId, Locale};
+use birthday_greetings_kata_rust::friends::friends_gateway::{
+    FriendsGateway, FriendsGatewayError, FriendsLoadReport, InvalidFriendRecord, MatchedDateFormat,
+    RejectedRecord, WritableFriendsGateway,
+};
 use chrono::NaiveDate;
+use chrono_tz::Tz;
+use std::fs;
 use std::io::Result;
 use std::io::Write;
-use tempfile::NamedTempFile;
+use tempfile::{tempdir, NamedTempFile};
 
 #[test]
 fn get_friends_from_flat_file() -> Result<()> {
@@ -23,7 +32,7 @@ fn get_friends_from_flat_file() -> Result<()> {
     )?;
     let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
 
-    let friends = flat_file_friends_gateway.get_friends();
+    let friends = flat_file_friends_gateway.get_friends().unwrap();
 
     assert_eq!(
         friends,
@@ -53,8 +62,906 @@ fn get_no_friends_from_empty_flat_file() -> Result<()> {
     writeln!(temp_file, "last_name, first_name, date_of_birth, email")?;
     let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
 
-    let friends = flat_file_friends_gateway.get_friends();
+    let friends = flat_file_friends_gateway.get_friends().unwrap();
 
     assert_eq!(friends, Vec::new());
     Ok(())
 }
+
+#[test]
+fn fail_to_get_friends_when_a_row_has_a_missing_column() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    writeln!(
+        temp_file,
+        "Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990"
+    )?;
+    writeln!(temp_file, "Germi, Mario, 11/12/1980, mario@germi.com")?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = flat_file_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidRecord {
+            line: 3,
+            error: InvalidFriendRecord::MissingField("phone_number".to_string())
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn fail_to_get_friends_when_a_row_has_an_invalid_date() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    writeln!(
+        temp_file,
+        "Franchi, Franca, 31/02/1970, franca@franchi.com, 3398889990"
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = flat_file_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidRecord {
+            line: 2,
+            error: InvalidFriendRecord::InvalidDate("31/02/1970".to_string())
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn fail_to_get_friends_when_a_row_is_not_valid_utf8() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    temp_file.write_all(b"Fran\xe7hi, Franca, 24/08/1970, franca@franchi.com, 3398889990\n")?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = flat_file_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidRecord {
+            line: 2,
+            error: InvalidFriendRecord::InvalidEncoding
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn skip_invalid_rows_and_report_them_when_loading_leniently() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    writeln!(
+        temp_file,
+        "Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990"
+    )?;
+    writeln!(
+        temp_file,
+        "Germi, Mario, 11/13/1980, mario@germi.com, 3396665559"
+    )?;
+    writeln!(temp_file, "Doe, Mary, 24/08/1982, mary@doe.com")?;
+    let mut flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+    flat_file_friends_gateway.configure_loading_mode(LoadingMode::Lenient);
+
+    let (friends, report) = flat_file_friends_gateway.get_friends_with_report().unwrap();
+
+    assert_eq!(
+        friends,
+        vec![FriendData::new(
+            "Franca",
+            "Franchi",
+            NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+            "franca@franchi.com",
+            "3398889990"
+        )]
+    );
+    assert_eq!(
+        report,
+        FriendsLoadReport::new(vec![
+            RejectedRecord::new(
+                3,
+                "Germi, Mario, 11/13/1980, mario@germi.com, 3396665559",
+                InvalidFriendRecord::InvalidDate("11/13/1980".to_string())
+            ),
+            RejectedRecord::new(
+                4,
+                "Doe, Mary, 24/08/1982, mary@doe.com",
+                InvalidFriendRecord::MissingField("phone_number".to_string())
+            )
+        ])
+        .with_matched_date_formats(vec![MatchedDateFormat::new(2, DateFormat::european())])
+    );
+    Ok(())
+}
+
+#[test]
+fn get_friends_with_quoted_fields_containing_commas() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    writeln!(
+        temp_file,
+        "\"De Luca, Jr.\", Mario, 11/12/1980, mario@deluca.com, 3396665559"
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = flat_file_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends,
+        vec![FriendData::new(
+            "Mario",
+            "De Luca, Jr.",
+            NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+            "mario@deluca.com",
+            "3396665559"
+        )]
+    );
+    Ok(())
+}
+
+#[test]
+fn get_friends_with_escaped_quotes_in_quoted_fields() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    writeln!(
+        temp_file,
+        "Germi, \"Mario \"\"Super\"\"\", 11/12/1980, mario@germi.com, 3396665559"
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = flat_file_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends,
+        vec![FriendData::new(
+            "Mario \"Super\"",
+            "Germi",
+            NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+            "mario@germi.com",
+            "3396665559"
+        )]
+    );
+    Ok(())
+}
+
+#[test]
+fn get_friends_from_flat_file_with_crlf_line_endings() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    temp_file.write_all(
+        b"last_name, first_name, date_of_birth, email, phone_number\r\n\
+          Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990\r\n\
+          Germi, Mario, 11/12/1980, mario@germi.com, 3396665559\r\n",
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = flat_file_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends,
+        vec![
+            FriendData::new(
+                "Franca",
+                "Franchi",
+                NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+                "franca@franchi.com",
+                "3398889990"
+            ),
+            FriendData::new(
+                "Mario",
+                "Germi",
+                NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+                "mario@germi.com",
+                "3396665559"
+            )
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn get_friends_from_flat_file_starting_with_utf8_bom() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    temp_file.write_all(b"\xEF\xBB\xBF")?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    writeln!(
+        temp_file,
+        "Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990"
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = flat_file_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends,
+        vec![FriendData::new(
+            "Franca",
+            "Franchi",
+            NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+            "franca@franchi.com",
+            "3398889990"
+        )]
+    );
+    Ok(())
+}
+
+#[test]
+fn ignore_blank_trailing_lines() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    writeln!(
+        temp_file,
+        "Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990"
+    )?;
+    writeln!(temp_file)?;
+    writeln!(temp_file, "   ")?;
+    writeln!(temp_file)?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = flat_file_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends,
+        vec![FriendData::new(
+            "Franca",
+            "Franchi",
+            NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+            "franca@franchi.com",
+            "3398889990"
+        )]
+    );
+    Ok(())
+}
+
+#[test]
+fn report_the_starting_line_of_rows_following_multiline_quoted_fields() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    writeln!(
+        temp_file,
+        "\"Franchi\nde Franchis\", Franca, 24/08/1970, franca@franchi.com, 3398889990"
+    )?;
+    writeln!(
+        temp_file,
+        "Germi, Mario, 11/13/1980, mario@germi.com, 3396665559"
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = flat_file_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidRecord {
+            line: 4,
+            error: InvalidFriendRecord::InvalidDate("11/13/1980".to_string())
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn fail_to_get_friends_when_a_quoted_field_is_not_terminated() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    writeln!(
+        temp_file,
+        "\"Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990"
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = flat_file_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidRecord {
+            line: 2,
+            error: InvalidFriendRecord::MalformedCsv("unterminated quoted field".to_string())
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn get_friends_from_flat_file_with_reordered_and_unknown_columns() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "email, nickname, first_name, last_name, phone_number, date_of_birth"
+    )?;
+    writeln!(
+        temp_file,
+        "franca@franchi.com, Fra, Franca, Franchi, 3398889990, 24/08/1970"
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = flat_file_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends,
+        vec![FriendData::new(
+            "Franca",
+            "Franchi",
+            NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+            "franca@franchi.com",
+            "3398889990"
+        )]
+    );
+    Ok(())
+}
+
+#[test]
+fn get_friends_from_flat_file_with_custom_column_mapping() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(temp_file, "Surname, Name, Birthday, E-Mail, Mobile")?;
+    writeln!(
+        temp_file,
+        "Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990"
+    )?;
+    let mut flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+    flat_file_friends_gateway.configure_column_mapping("surname", FriendColumn::LastName);
+    flat_file_friends_gateway.configure_column_mapping("name", FriendColumn::FirstName);
+    flat_file_friends_gateway.configure_column_mapping("birthday", FriendColumn::DateOfBirth);
+    flat_file_friends_gateway.configure_column_mapping("e-mail", FriendColumn::Email);
+    flat_file_friends_gateway.configure_column_mapping("mobile", FriendColumn::PhoneNumber);
+
+    let friends = flat_file_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends,
+        vec![FriendData::new(
+            "Franca",
+            "Franchi",
+            NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+            "franca@franchi.com",
+            "3398889990"
+        )]
+    );
+    Ok(())
+}
+
+#[test]
+fn fail_to_get_friends_when_the_header_lacks_a_column() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(temp_file, "last_name, first_name, date_of_birth, email")?;
+    writeln!(
+        temp_file,
+        "Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990"
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = flat_file_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidRecord {
+            line: 2,
+            error: InvalidFriendRecord::MissingField("phone_number".to_string())
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn get_friends_with_birthdates_in_configured_formats() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    writeln!(
+        temp_file,
+        "Franchi, Franca, 1970-08-24, franca@franchi.com, 3398889990"
+    )?;
+    writeln!(
+        temp_file,
+        "Germi, Mario, 11.12.1980, mario@germi.com, 3396665559"
+    )?;
+    let mut flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+    flat_file_friends_gateway.configure_birthdate_parser(BirthdateParser::new(vec![
+        DateFormat::iso(),
+        DateFormat::dotted(),
+    ]));
+
+    let (friends, report) = flat_file_friends_gateway.get_friends_with_report().unwrap();
+
+    assert_eq!(
+        friends,
+        vec![
+            FriendData::new(
+                "Franca",
+                "Franchi",
+                NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+                "franca@franchi.com",
+                "3398889990"
+            ),
+            FriendData::new(
+                "Mario",
+                "Germi",
+                NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+                "mario@germi.com",
+                "3396665559"
+            )
+        ]
+    );
+    assert_eq!(
+        report.matched_date_formats,
+        vec![
+            MatchedDateFormat::new(2, DateFormat::iso()),
+            MatchedDateFormat::new(3, DateFormat::dotted())
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn fail_to_get_friends_with_ambiguous_birthdates() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    writeln!(
+        temp_file,
+        "Franchi, Franca, 03/04/1990, franca@franchi.com, 3398889990"
+    )?;
+    let mut flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+    flat_file_friends_gateway.configure_birthdate_parser(BirthdateParser::new(vec![
+        DateFormat::european(),
+        DateFormat::us(),
+    ]));
+
+    let friends = flat_file_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidRecord {
+            line: 2,
+            error: InvalidFriendRecord::AmbiguousDate("03/04/1990".to_string())
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn get_friends_with_unknown_birth_year() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    writeln!(
+        temp_file,
+        "Franchi, Franca, 24/08, franca@franchi.com, 3398889990"
+    )?;
+    writeln!(
+        temp_file,
+        "Germi, Mario, --12-11, mario@germi.com, 3396665559"
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = flat_file_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends,
+        vec![
+            FriendData::new(
+                "Franca",
+                "Franchi",
+                Birthdate::from_md_opt(8, 24).unwrap(),
+                "franca@franchi.com",
+                "3398889990"
+            ),
+            FriendData::new(
+                "Mario",
+                "Germi",
+                Birthdate::from_md_opt(12, 11).unwrap(),
+                "mario@germi.com",
+                "3396665559"
+            )
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn add_friends_with_sequential_ids_keeping_the_derived_ids_of_existing_ones() -> Result<()> {
+    let directory = tempdir()?;
+    let path = directory.path().join("friends.csv");
+    fs::write(
+        &path,
+        "last_name, first_name, date_of_birth, email, phone_number\n\
+         Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990\n",
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::open(&path);
+
+    let id = flat_file_friends_gateway
+        .add_friend(FriendData::new(
+            "Mario",
+            "Germi",
+            Birthdate::from_md_opt(12, 11).unwrap(),
+            "mario@germi.com",
+            "3396665559",
+        ))
+        .unwrap();
+
+    assert_eq!(id, FriendId::new("1"));
+    assert_eq!(
+        fs::read_to_string(&path)?,
+        "id, last_name, first_name, date_of_birth, email, phone_number\n\
+         franchi-franca-19700824-franca@franchi.com, Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990\n\
+         1, Germi, Mario, 11/12, mario@germi.com, 3396665559\n"
+    );
+    assert_eq!(fs::read_dir(directory.path())?.count(), 1);
+    Ok(())
+}
+
+#[test]
+fn update_and_remove_friends_by_id() -> Result<()> {
+    let directory = tempdir()?;
+    let path = directory.path().join("friends.csv");
+    fs::write(
+        &path,
+        "id, last_name, first_name, date_of_birth, email, phone_number\n\
+         7, Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990\n\
+         9, Germi, Mario, 11/12/1980, mario@germi.com, 3396665559\n",
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::open(&path);
+
+    flat_file_friends_gateway
+        .update_friend(
+            &FriendId::new("9"),
+            FriendData::new(
+                "Mario",
+                "Germi, Jr.",
+                NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+                "mario@germi.com",
+                "3396665559",
+            ),
+        )
+        .unwrap();
+    flat_file_friends_gateway
+        .remove_friend(&FriendId::new("7"))
+        .unwrap();
+
+    assert_eq!(
+        flat_file_friends_gateway.get_friends().unwrap(),
+        vec![FriendData::new(
+            "Mario",
+            "Germi, Jr.",
+            NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+            "mario@germi.com",
+            "3396665559",
+        )
+        .with_id(FriendId::new("9"))]
+    );
+    assert_eq!(
+        fs::read_to_string(&path)?,
+        "id, last_name, first_name, date_of_birth, email, phone_number\n\
+         9, \"Germi, Jr.\", Mario, 11/12/1980, mario@germi.com, 3396665559\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn keep_header_names_column_order_and_unknown_columns_when_rewriting() -> Result<()> {
+    let directory = tempdir()?;
+    let path = directory.path().join("friends.csv");
+    fs::write(
+        &path,
+        "surname, first_name, date_of_birth, email, phone_number, nickname\n\
+         Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990, Frà\n",
+    )?;
+    let mut flat_file_friends_gateway = FlatFileFriendsGateway::open(&path);
+    flat_file_friends_gateway.configure_column_mapping("surname", FriendColumn::LastName);
+
+    flat_file_friends_gateway
+        .add_friend(FriendData::new(
+            "Mario",
+            "Germi",
+            NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+            "mario@germi.com",
+            "3396665559",
+        ))
+        .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&path)?,
+        "id, surname, first_name, date_of_birth, email, phone_number, nickname\n\
+         franchi-franca-19700824-franca@franchi.com, Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990, Frà\n\
+         1, Germi, Mario, 11/12/1980, mario@germi.com, 3396665559, \n"
+    );
+    assert_eq!(flat_file_friends_gateway.get_friends().unwrap().len(), 2);
+    Ok(())
+}
+
+#[test]
+fn give_homonyms_born_on_the_same_day_distinct_ids_and_keep_them_writable() -> Result<()> {
+    let directory = tempdir()?;
+    let path = directory.path().join("friends.csv");
+    fs::write(
+        &path,
+        "last_name, first_name, date_of_birth, email, phone_number\n\
+         Rossi, Mario, 11/12/1980, mario@rossi.com, 3396665559\n\
+         Rossi, Mario, 11/12/1980, mario.rossi@email.com, 3396665550\n",
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::open(&path);
+
+    let ids: Vec<FriendId> = flat_file_friends_gateway
+        .get_friends()
+        .unwrap()
+        .into_iter()
+        .map(|friend| friend.id)
+        .collect();
+    flat_file_friends_gateway
+        .remove_friend(&FriendId::new("rossi-mario-19801211-mario.rossi@email.com"))
+        .unwrap();
+
+    assert_eq!(
+        ids,
+        vec![
+            FriendId::new("rossi-mario-19801211-mario@rossi.com"),
+            FriendId::new("rossi-mario-19801211-mario.rossi@email.com")
+        ]
+    );
+    assert_eq!(
+        fs::read_to_string(&path)?,
+        "id, last_name, first_name, date_of_birth, email, phone_number\n\
+         rossi-mario-19801211-mario@rossi.com, Rossi, Mario, 11/12/1980, mario@rossi.com, 3396665559\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn give_homonyms_the_same_ids_whatever_their_order_in_the_file() -> Result<()> {
+    let directory = tempdir()?;
+    let path = directory.path().join("friends.csv");
+    fs::write(
+        &path,
+        "last_name, first_name, date_of_birth, email, phone_number\n\
+         Rossi, Mario, 11/12/1980, mario.rossi@email.com, 3396665550\n\
+         Rossi, Mario, 11/12/1980, mario@rossi.com, 3396665559\n",
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::open(&path);
+
+    let ids: Vec<FriendId> = flat_file_friends_gateway
+        .get_friends()
+        .unwrap()
+        .into_iter()
+        .map(|friend| friend.id)
+        .collect();
+
+    assert_eq!(
+        ids,
+        vec![
+            FriendId::new("rossi-mario-19801211-mario.rossi@email.com"),
+            FriendId::new("rossi-mario-19801211-mario@rossi.com")
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn fail_to_update_a_missing_friend() -> Result<()> {
+    let directory = tempdir()?;
+    let path = directory.path().join("friends.csv");
+    fs::write(
+        &path,
+        "last_name, first_name, date_of_birth, email, phone_number\n",
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::open(&path);
+
+    let result = flat_file_friends_gateway.remove_friend(&FriendId::new("1"));
+
+    assert_eq!(
+        result,
+        Err(FriendsGatewayError::FriendNotFound(FriendId::new("1")))
+    );
+    Ok(())
+}
+
+#[test]
+fn fail_to_rewrite_a_flat_file_with_invalid_rows_even_when_loading_leniently() -> Result<()> {
+    let directory = tempdir()?;
+    let path = directory.path().join("friends.csv");
+    let content = "last_name, first_name, date_of_birth, email, phone_number\n\
+                   Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990\n\
+                   Germi, Mario, 11/13/1980, mario@germi.com, 3396665559\n";
+    fs::write(&path, content)?;
+    let mut flat_file_friends_gateway = FlatFileFriendsGateway::open(&path);
+    flat_file_friends_gateway.configure_loading_mode(LoadingMode::Lenient);
+
+    let result = flat_file_friends_gateway.remove_friend(&FriendId::new("1"));
+
+    assert_eq!(
+        result,
+        Err(FriendsGatewayError::InvalidRecord {
+            line: 3,
+            error: InvalidFriendRecord::InvalidDate("11/13/1980".to_owned())
+        })
+    );
+    assert_eq!(fs::read_to_string(&path)?, content);
+    Ok(())
+}
+
+#[test]
+fn fail_to_write_a_flat_file_not_opened_from_a_path() -> Result<()> {
+    let temp_file = NamedTempFile::new()?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let result = flat_file_friends_gateway.add_friend(FriendData::new(
+        "Mario",
+        "Germi",
+        NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+        "mario@germi.com",
+        "3396665559",
+    ));
+
+    assert!(matches!(result, Err(FriendsGatewayError::Write(_))));
+    Ok(())
+}
+
+#[test]
+fn identify_friends_by_id_column_or_by_derived_key() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "id, last_name, first_name, date_of_birth, email, phone_number"
+    )?;
+    writeln!(
+        temp_file,
+        "f-42, Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990"
+    )?;
+    writeln!(
+        temp_file,
+        ", D'Amico, Anna Maria, 11/12, anna@damico.com, 3396665559"
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = flat_file_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends.iter().map(|f| f.id.clone()).collect::<Vec<_>>(),
+        vec![
+            FriendId::new("f-42"),
+            FriendId::new("d-amico-anna-maria-1211-anna@damico.com")
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn get_friends_with_their_time_zone() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number, time_zone"
+    )?;
+    writeln!(
+        temp_file,
+        "Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990, Asia/Tokyo"
+    )?;
+    writeln!(
+        temp_file,
+        "Germi, Mario, 11/12/1980, mario@germi.com, 3396665559,"
+    )?;
+    writeln!(
+        temp_file,
+        "Doe, Mary, 24/08/1982, mary@doe.com, 3396665559, Mars/Olympus_Mons"
+    )?;
+    let mut flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+    flat_file_friends_gateway.configure_loading_mode(LoadingMode::Lenient);
+
+    let (friends, report) = flat_file_friends_gateway.get_friends_with_report().unwrap();
+
+    assert_eq!(
+        friends.iter().map(|f| f.time_zone).collect::<Vec<_>>(),
+        vec![Some(Tz::Asia__Tokyo), None]
+    );
+    assert_eq!(
+        report.rejected_records[0].reason,
+        InvalidFriendRecord::InvalidTimeZone("Mars/Olympus_Mons".to_owned())
+    );
+    Ok(())
+}
+
+#[test]
+fn write_the_time_zone_column_only_when_a_friend_has_one() -> Result<()> {
+    let directory = tempdir()?;
+    let path = directory.path().join("friends.csv");
+    fs::write(
+        &path,
+        "id, last_name, first_name, date_of_birth, email, phone_number\n\
+         1, Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990\n",
+    )?;
+    let flat_file_friends_gateway = FlatFileFriendsGateway::open(&path);
+
+    flat_file_friends_gateway
+        .add_friend(
+            FriendData::new(
+                "Mario",
+                "Germi",
+                NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+                "mario@germi.com",
+                "3396665559",
+            )
+            .with_time_zone(Tz::America__New_York),
+        )
+        .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&path)?,
+        "id, last_name, first_name, date_of_birth, email, phone_number, time_zone\n\
+         1, Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990, \n\
+         2, Germi, Mario, 11/12/1980, mario@germi.com, 3396665559, America/New_York\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn get_friends_with_their_locale() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    writeln!(
+        temp_file,
+        "last_name, first_name, date_of_birth, email, phone_number, locale"
+    )?;
+    writeln!(
+        temp_file,
+        "Franchi, Franca, 24/08/1970, franca@franchi.com, 3398889990, it_ch"
+    )?;
+    writeln!(
+        temp_file,
+        "Germi, Mario, 11/12/1980, mario@germi.com, 3396665559,"
+    )?;
+    writeln!(
+        temp_file,
+        "Doe, Mary, 24/08/1982, mary@doe.com, 3396665559, Klingon"
+    )?;
+    let mut flat_file_friends_gateway = FlatFileFriendsGateway::new(temp_file.reopen()?);
+    flat_file_friends_gateway.configure_loading_mode(LoadingMode::Lenient);
+
+    let (friends, report) = flat_file_friends_gateway.get_friends_with_report().unwrap();
+
+    assert_eq!(
+        friends.iter().map(|f| f.locale.clone()).collect::<Vec<_>>(),
+        vec![Locale::parse("it-CH"), None]
+    );
+    assert_eq!(
+        report.rejected_records[0].reason,
+        InvalidFriendRecord::InvalidLocale("Klingon".to_owned())
+    );
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use std::{cell::RefCell, rc::Rc};
 
+use birthday_greetings_kata_rust::friends::friend_data::FriendId;
 use birthday_greetings_kata_rust::greetings::{
     greeting::Greeting,
     greetings_sender::{GreetingsSender, SendGreetingsError},
@@ -40,8 +41,20 @@ fn send_greetings_using_all_the_given_greetings_senders() {
     ]);
 
     let greetings = vec![
-        Greeting::new("Franco", "Franchi", "franco@franchi.com", "3398889990"),
-        Greeting::new("Mary", "Doe", "mary@doe.com", "3396665559"),
+        Greeting::new(
+            &FriendId::new("franchi-franco"),
+            "Franco",
+            "Franchi",
+            "franco@franchi.com",
+            "3398889990",
+        ),
+        Greeting::new(
+            &FriendId::new("doe-mary"),
+            "Mary",
+            "Doe",
+            "mary@doe.com",
+            "3396665559",
+        ),
     ];
     let _ = greetings_sender_announcer.send(greetings.clone());
 
@@ -0,0 +1,34 @@
+use birthday_greetings_kata_rust::greetings::emails::html_text::html_to_text;
+
+#[test]
+fn derive_paragraphs_and_lines_from_html() {
+    let html = "<html><head><title>Card</title><style>p { color: red; }</style></head>\n\
+                <body>\n  <h1>Happy   birthday,\n dear Mary!</h1>\n\
+                <p>Many happy<br>returns &amp; best wishes&nbsp;&#8212; from all of us.</p>\n\
+                <ul><li>Cake</li><li>Candles</li></ul><!-- tracking --></body></html>";
+
+    assert_eq!(
+        html_to_text(html),
+        "Happy birthday, dear Mary!\n\nMany happy\nreturns & best wishes \u{2014} from all of us.\n\n- Cake\n- Candles"
+    );
+}
+
+#[test]
+fn keep_image_descriptions_and_link_targets() {
+    let html = "<p><img src=\"cid:birthday-card\" alt=\"A birthday cake\"/></p>\
+                <p>See <a href=\"https://example.org/party\">the party</a> or \
+                <a href='https://example.org'>https://example.org</a></p>";
+
+    assert_eq!(
+        html_to_text(html),
+        "A birthday cake\n\nSee the party (https://example.org/party) or https://example.org"
+    );
+}
+
+#[test]
+fn leave_plain_text_and_unknown_entities_untouched() {
+    assert_eq!(
+        html_to_text("Happy birthday & cheers &unknown; 1 < 2"),
+        "Happy birthday & cheers &unknown; 1 < 2"
+    );
+}
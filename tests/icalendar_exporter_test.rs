@@ -0,0 +1,191 @@
+use birthday_greetings_kata_rust::{
+    calendars::fixed_calendar::FixedCalendar,
+    friends::{
+        birthdate::{Birthdate, LeapDayPolicy},
+        friend_data::{FriendData, FriendId},
+        friends_gateway::{FriendsGateway, FriendsGatewayError, InvalidFriendRecord},
+    },
+    icalendar_exporter::{ICalendarExportError, ICalendarExporter},
+};
+use chrono::NaiveDate;
+use std::rc::Rc;
+
+struct FriendsGatewayStub {
+    friends: Result<Vec<FriendData>, FriendsGatewayError>,
+}
+
+impl FriendsGateway for FriendsGatewayStub {
+    fn get_friends(&self) -> Result<Vec<FriendData>, FriendsGatewayError> {
+        self.friends.clone()
+    }
+}
+
+fn export(
+    friends: Result<Vec<FriendData>, FriendsGatewayError>,
+) -> Result<String, ICalendarExportError> {
+    export_with_leap_day_policy(friends, LeapDayPolicy::February28)
+}
+
+fn export_with_leap_day_policy(
+    friends: Result<Vec<FriendData>, FriendsGatewayError>,
+    leap_day_policy: LeapDayPolicy,
+) -> Result<String, ICalendarExportError> {
+    let mut exporter = ICalendarExporter::new(
+        Rc::new(FriendsGatewayStub { friends }),
+        Rc::new(FixedCalendar::new(
+            NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(),
+        )),
+    );
+    exporter.configure_leap_day_policy(leap_day_policy);
+    let mut output = Vec::new();
+    exporter.export(&mut output)?;
+    Ok(String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn export_a_yearly_recurring_event_per_friend() {
+    let ics = export(Ok(vec![
+        FriendData::new(
+            "Franca",
+            "Franchi",
+            NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+            "franca@franchi.com",
+            "3398889990",
+        ),
+        FriendData::new(
+            "Mario",
+            "Germi",
+            Birthdate::from_md_opt(12, 11).unwrap(),
+            "mario@germi.com",
+            "3396665559",
+        ),
+    ]));
+
+    assert_eq!(
+        ics,
+        Ok("BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            PRODID:-//birthday-greetings-kata-rust//Birthdays//EN\r\n\
+            CALSCALE:GREGORIAN\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:franchi-franca-19700824-franca@franchi.com@birthday-greetings\r\n\
+            DTSTAMP:20230824T000000Z\r\n\
+            DTSTART;VALUE=DATE:19700824\r\n\
+            RRULE:FREQ=YEARLY\r\n\
+            SUMMARY:Franca Franchi's birthday\r\n\
+            TRANSP:TRANSPARENT\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:germi-mario-1211-mario@germi.com@birthday-greetings\r\n\
+            DTSTAMP:20230824T000000Z\r\n\
+            DTSTART;VALUE=DATE:20001211\r\n\
+            RRULE:FREQ=YEARLY\r\n\
+            SUMMARY:Mario Germi's birthday\r\n\
+            TRANSP:TRANSPARENT\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n"
+            .to_string())
+    );
+}
+
+#[test]
+fn export_february_29th_birthdays_recurring_on_the_last_day_of_february() {
+    let ics = export(Ok(vec![FriendData::new(
+        "Carla",
+        "Sandri",
+        NaiveDate::from_ymd_opt(1984, 2, 29).unwrap(),
+        "carla@sandri.com",
+        "3335556667",
+    )]))
+    .unwrap();
+
+    assert!(ics.contains("DTSTART;VALUE=DATE:19840229\r\n"));
+    assert!(ics.contains("RRULE:FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=-1\r\n"));
+}
+
+#[test]
+fn export_february_29th_birthdays_following_the_leap_day_policy() {
+    let carla = || {
+        Ok(vec![FriendData::new(
+            "Carla",
+            "Sandri",
+            NaiveDate::from_ymd_opt(1984, 2, 29).unwrap(),
+            "carla@sandri.com",
+            "3335556667",
+        )])
+    };
+
+    let on_march_1st = export_with_leap_day_policy(carla(), LeapDayPolicy::March1).unwrap();
+    let skipped = export_with_leap_day_policy(carla(), LeapDayPolicy::Skip).unwrap();
+
+    assert!(on_march_1st.contains("DTSTART;VALUE=DATE:19840229\r\n"));
+    assert!(on_march_1st.contains("RRULE:FREQ=YEARLY;BYYEARDAY=60\r\n"));
+    assert!(skipped.contains("DTSTART;VALUE=DATE:19840229\r\n"));
+    assert!(skipped.contains("RRULE:FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=29\r\n"));
+}
+
+#[test]
+fn identify_each_event_by_the_id_of_its_friend() {
+    let mario = |id: &str| {
+        FriendData::new(
+            "Mario",
+            "Rossi",
+            NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+            "mario@rossi.com",
+            "3396665559",
+        )
+        .with_id(FriendId::new(id))
+    };
+
+    let ics = export(Ok(vec![mario("1"), mario("urn:uuid:4fbe8971,0bc3")])).unwrap();
+
+    assert!(ics.contains("UID:1@birthday-greetings\r\n"));
+    assert!(ics.contains("UID:urn:uuid:4fbe8971\\,0bc3@birthday-greetings\r\n"));
+}
+
+#[test]
+fn export_an_empty_calendar_when_there_is_no_friend() {
+    let ics = export(Ok(Vec::new()));
+
+    assert_eq!(
+        ics,
+        Ok("BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            PRODID:-//birthday-greetings-kata-rust//Birthdays//EN\r\n\
+            CALSCALE:GREGORIAN\r\n\
+            END:VCALENDAR\r\n"
+            .to_string())
+    );
+}
+
+#[test]
+fn escape_and_fold_long_summaries() {
+    let ics = export(Ok(vec![FriendData::new(
+        "Maria Benedetta Anna Luisa",
+        "De Luca, Jr.; della Valle di Sopra",
+        NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+        "maria@deluca.com",
+        "3398889990",
+    )]))
+    .unwrap();
+
+    assert!(ics.contains(
+        "SUMMARY:Maria Benedetta Anna Luisa De Luca\\, Jr.\\; della Valle di Sopra's b\r\n irthday\r\n"
+    ));
+    assert!(ics.lines().all(|line| line.len() <= 75));
+}
+
+#[test]
+fn fail_to_export_when_friends_cannot_be_loaded() {
+    let loading_error = FriendsGatewayError::InvalidRecord {
+        line: 2,
+        error: InvalidFriendRecord::InvalidDate("31/02/1970".to_string()),
+    };
+
+    let ics = export(Err(loading_error.clone()));
+
+    assert_eq!(
+        ics,
+        Err(ICalendarExportError::FriendsNotLoaded(loading_error))
+    );
+}
@@ -0,0 +1,299 @@
+use birthday_greetings_kata_rust::friends::birthdate::Birthdate;
+use birthday_greetings_kata_rust::friends::birthdate_parser::{BirthdateParser, DateFormat};
+use birthday_greetings_kata_rust::friends::friend_data::{FriendData, FriendId, Locale};
+use birthday_greetings_kata_rust::friends::friends_gateway::{
+    FriendsGateway, FriendsGatewayError, InvalidFriendRecord, MatchedDateFormat,
+};
+use birthday_greetings_kata_rust::friends::json_friends_gateway::JsonFriendsGateway;
+use chrono::NaiveDate;
+use std::io::{Cursor, Result, Write};
+use tempfile::NamedTempFile;
+
+#[test]
+fn get_friends_from_json_file() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    write!(
+        temp_file,
+        r#"[
+            {{
+                "name": "Franca",
+                "surname": "Franchi",
+                "birthdate": "24/08/1970",
+                "email": "franca@franchi.com",
+                "phone_number": "3398889990"
+            }},
+            {{
+                "name": "Mario",
+                "surname": "Germi",
+                "birthdate": "11/12",
+                "email": "mario@germi.com",
+                "phone_number": "3396665559",
+                "department": "Sales"
+            }}
+        ]"#
+    )?;
+    let json_friends_gateway = JsonFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = json_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends,
+        vec![
+            FriendData::new(
+                "Franca",
+                "Franchi",
+                NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+                "franca@franchi.com",
+                "3398889990"
+            ),
+            FriendData::new(
+                "Mario",
+                "Germi",
+                Birthdate::from_md_opt(12, 11).unwrap(),
+                "mario@germi.com",
+                "3396665559"
+            )
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn get_no_friends_from_empty_json_array() {
+    let json_friends_gateway = JsonFriendsGateway::from_reader(Cursor::new("[]"));
+
+    let friends = json_friends_gateway.get_friends();
+
+    assert_eq!(friends, Ok(Vec::new()))
+}
+
+#[test]
+fn fail_to_get_friends_from_malformed_json() {
+    let json_friends_gateway = JsonFriendsGateway::from_reader(Cursor::new("[{\"name\": }]"));
+
+    let friends = json_friends_gateway.get_friends();
+
+    assert!(matches!(friends, Err(FriendsGatewayError::InvalidJson(_))))
+}
+
+#[test]
+fn fail_to_get_friends_when_json_is_not_an_array() {
+    let json_friends_gateway = JsonFriendsGateway::from_reader(Cursor::new("{}"));
+
+    let friends = json_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidJson(
+            "expected an array of friends".to_string()
+        ))
+    )
+}
+
+#[test]
+fn fail_to_get_friends_pointing_at_the_entry_missing_a_field() {
+    let json_friends_gateway = JsonFriendsGateway::from_reader(Cursor::new(
+        r#"[
+            {
+                "name": "Franca",
+                "surname": "Franchi",
+                "birthdate": "24/08/1970",
+                "email": "franca@franchi.com",
+                "phone_number": "3398889990"
+            },
+            {
+                "name": "Mario",
+                "surname": "Germi",
+                "birthdate": "11/12/1980",
+                "phone_number": "3396665559"
+            }
+        ]"#,
+    ));
+
+    let friends = json_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidEntry {
+            index: 1,
+            error: InvalidFriendRecord::MissingField("email".to_string())
+        })
+    )
+}
+
+#[test]
+fn fail_to_get_friends_pointing_at_the_entry_with_an_invalid_birthdate() {
+    let json_friends_gateway = JsonFriendsGateway::from_reader(Cursor::new(
+        r#"[
+            {
+                "name": "Franca",
+                "surname": "Franchi",
+                "birthdate": "1970-08-24",
+                "email": "franca@franchi.com",
+                "phone_number": "3398889990"
+            }
+        ]"#,
+    ));
+
+    let friends = json_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidEntry {
+            index: 0,
+            error: InvalidFriendRecord::InvalidDate("1970-08-24".to_string())
+        })
+    )
+}
+
+#[test]
+fn fail_to_get_friends_pointing_at_the_entry_with_a_field_of_the_wrong_type() {
+    let json_friends_gateway = JsonFriendsGateway::from_reader(Cursor::new(
+        r#"[
+            {
+                "name": "Franca",
+                "surname": "Franchi",
+                "birthdate": "24/08/1970",
+                "email": "franca@franchi.com",
+                "phone_number": 3398889990
+            }
+        ]"#,
+    ));
+
+    let friends = json_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidEntry {
+            index: 0,
+            error: InvalidFriendRecord::InvalidField("phone_number".to_string())
+        })
+    )
+}
+
+#[test]
+fn fail_to_get_friends_when_an_entry_is_not_an_object() {
+    let json_friends_gateway = JsonFriendsGateway::from_reader(Cursor::new("[\"Franca\"]"));
+
+    let friends = json_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidEntry {
+            index: 0,
+            error: InvalidFriendRecord::NotAnObject
+        })
+    )
+}
+
+#[test]
+fn identify_friends_by_their_string_or_numeric_id() {
+    let json_friends_gateway = JsonFriendsGateway::from_reader(Cursor::new(
+        r#"[
+            {
+                "id": "franca",
+                "name": "Franca",
+                "surname": "Franchi",
+                "birthdate": "24/08/1970",
+                "email": "franca@franchi.com",
+                "phone_number": "3398889990"
+            },
+            {
+                "id": 7,
+                "name": "Mario",
+                "surname": "Germi",
+                "birthdate": "11/12/1980",
+                "email": "mario@germi.com",
+                "phone_number": "3396665559"
+            }
+        ]"#,
+    ));
+
+    let friends = json_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends.iter().map(|f| f.id.clone()).collect::<Vec<_>>(),
+        vec![FriendId::new("franca"), FriendId::new("7")]
+    );
+}
+
+#[test]
+fn fail_to_get_friends_with_an_unknown_time_zone() {
+    let json_friends_gateway = JsonFriendsGateway::from_reader(Cursor::new(
+        r#"[
+            {
+                "name": "Franca",
+                "surname": "Franchi",
+                "birthdate": "24/08/1970",
+                "email": "franca@franchi.com",
+                "phone_number": "3398889990",
+                "time_zone": "Asia/Tokyo"
+            },
+            {
+                "name": "Mario",
+                "surname": "Germi",
+                "birthdate": "11/12/1980",
+                "email": "mario@germi.com",
+                "phone_number": "3396665559",
+                "time_zone": "UTC+2"
+            }
+        ]"#,
+    ));
+
+    let friends = json_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidEntry {
+            index: 1,
+            error: InvalidFriendRecord::InvalidTimeZone("UTC+2".to_owned())
+        })
+    );
+}
+
+#[test]
+fn get_friends_with_their_locale() {
+    let json_friends_gateway = JsonFriendsGateway::from_reader(Cursor::new(
+        r#"[
+            {
+                "name": "Franca",
+                "surname": "Franchi",
+                "birthdate": "24/08/1970",
+                "email": "franca@franchi.com",
+                "phone_number": "3398889990",
+                "locale": "de"
+            }
+        ]"#,
+    ));
+
+    let friends = json_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(friends[0].locale, Locale::parse("de"));
+}
+
+#[test]
+fn report_the_date_format_matched_by_each_entry() {
+    let mut json_friends_gateway = JsonFriendsGateway::from_reader(Cursor::new(
+        r#"[
+            {"name": "Franca", "surname": "Franchi", "birthdate": "1970-08-24",
+             "email": "franca@franchi.com", "phone_number": "3398889990"},
+            {"name": "Mario", "surname": "Germi", "birthdate": "11.12.1980",
+             "email": "mario@germi.com", "phone_number": "3396665559"}
+        ]"#,
+    ));
+    json_friends_gateway.configure_birthdate_parser(BirthdateParser::new(vec![
+        DateFormat::iso(),
+        DateFormat::dotted(),
+    ]));
+
+    let (friends, report) = json_friends_gateway.get_friends_with_report().unwrap();
+
+    assert_eq!(friends.len(), 2);
+    assert_eq!(
+        report.matched_date_formats,
+        vec![
+            MatchedDateFormat::new(0, DateFormat::iso()),
+            MatchedDateFormat::new(1, DateFormat::dotted())
+        ]
+    );
+}
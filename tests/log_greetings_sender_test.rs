@@ -1,10 +1,12 @@
 use std::{cell::RefCell, rc::Rc};
 
+use birthday_greetings_kata_rust::friends::friend_data::FriendId;
 use birthday_greetings_kata_rust::greetings::{
-    greeting::Greeting,
+    greeting::{Greeting, GreetingKind, Reminder},
     greetings_sender::{GreetingsSender, SendGreetingsError, SendSingleGreetingError},
     log_greetings_sender::LogGreetingsSender,
 };
+use chrono::NaiveDate;
 use log::Level;
 extern crate testing_logger;
 
@@ -39,8 +41,20 @@ fn log_info_sent_greetings() {
     greetings_sender.stub_sent_greetings_result(Ok(()));
 
     let greetings = vec![
-        Greeting::new("Carla", "Sandri", "carla@sandri.com", "3334445550"),
-        Greeting::new("Mario", "Verdi", "mario@verdi.com", "3336667770"),
+        Greeting::new(
+            &FriendId::new("sandri-carla"),
+            "Carla",
+            "Sandri",
+            "carla@sandri.com",
+            "3334445550",
+        ),
+        Greeting::new(
+            &FriendId::new("verdi-mario"),
+            "Mario",
+            "Verdi",
+            "mario@verdi.com",
+            "3336667770",
+        ),
     ];
     let send_result = log_greetings_sender.send(greetings);
 
@@ -60,8 +74,20 @@ fn log_info_only_sent_greetings() {
     let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
     let log_greetings_sender = LogGreetingsSender::new(Rc::clone(&greetings_sender));
 
-    let no_sent_greeting = Greeting::new("Carla", "Sandri", "carla@sandri.com", "3334445550");
-    let sent_greeting = Greeting::new("Mario", "Verdi", "mario@verdi.com", "3336667770");
+    let no_sent_greeting = Greeting::new(
+        &FriendId::new("sandri-carla"),
+        "Carla",
+        "Sandri",
+        "carla@sandri.com",
+        "3334445550",
+    );
+    let sent_greeting = Greeting::new(
+        &FriendId::new("verdi-mario"),
+        "Mario",
+        "Verdi",
+        "mario@verdi.com",
+        "3336667770",
+    );
 
     let send_greetings_error = SendGreetingsError::new(vec![(
         no_sent_greeting.clone(),
@@ -85,16 +111,40 @@ fn log_error_no_sent_greetings() {
     let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
     let log_greetings_sender = LogGreetingsSender::new(Rc::clone(&greetings_sender));
     let greetings = vec![
-        Greeting::new("Carla", "Sandri", "carla@sandri.com", "3334445550"),
-        Greeting::new("Mario", "Verdi", "mario@verdi.com", "3336667770"),
+        Greeting::new(
+            &FriendId::new("sandri-carla"),
+            "Carla",
+            "Sandri",
+            "carla@sandri.com",
+            "3334445550",
+        ),
+        Greeting::new(
+            &FriendId::new("verdi-mario"),
+            "Mario",
+            "Verdi",
+            "mario@verdi.com",
+            "3336667770",
+        ),
     ];
     let send_greetings_error = SendGreetingsError::new(vec![
         (
-            Greeting::new("Carla", "Sandri", "carla@sandri.com", "3334445550"),
+            Greeting::new(
+                &FriendId::new("sandri-carla"),
+                "Carla",
+                "Sandri",
+                "carla@sandri.com",
+                "3334445550",
+            ),
             SendSingleGreetingError::new("error".to_string()),
         ),
         (
-            Greeting::new("Mario", "Verdi", "mario@verdi.com", "3336667770"),
+            Greeting::new(
+                &FriendId::new("verdi-mario"),
+                "Mario",
+                "Verdi",
+                "mario@verdi.com",
+                "3336667770",
+            ),
             SendSingleGreetingError::new("error".to_string()),
         ),
     ]);
@@ -124,11 +174,29 @@ fn log_error_only_no_sent_greetings() {
     let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
     let log_greetings_sender = LogGreetingsSender::new(Rc::clone(&greetings_sender));
     let greetings = vec![
-        Greeting::new("Carla", "Sandri", "carla@sandri.com", "3334445550"),
-        Greeting::new("Mario", "Verdi", "mario@verdi.com", "3336667770"),
+        Greeting::new(
+            &FriendId::new("sandri-carla"),
+            "Carla",
+            "Sandri",
+            "carla@sandri.com",
+            "3334445550",
+        ),
+        Greeting::new(
+            &FriendId::new("verdi-mario"),
+            "Mario",
+            "Verdi",
+            "mario@verdi.com",
+            "3336667770",
+        ),
     ];
     let send_greetings_error = SendGreetingsError::new(vec![(
-        Greeting::new("Carla", "Sandri", "carla@sandri.com", "3334445550"),
+        Greeting::new(
+            &FriendId::new("sandri-carla"),
+            "Carla",
+            "Sandri",
+            "carla@sandri.com",
+            "3334445550",
+        ),
         SendSingleGreetingError::new("error".to_string()),
     )]);
     greetings_sender.stub_sent_greetings_result(Err(send_greetings_error.clone()));
@@ -145,3 +213,74 @@ fn log_error_only_no_sent_greetings() {
         assert_eq!(captured_logs[1].level, Level::Error);
     });
 }
+
+#[test]
+fn tell_apart_greetings_to_homonyms_by_friend_id() {
+    testing_logger::setup();
+    let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+    let log_greetings_sender = LogGreetingsSender::new(Rc::clone(&greetings_sender));
+
+    let no_sent_greeting = Greeting::new(
+        &FriendId::new("1"),
+        "Mario",
+        "Rossi",
+        "mario@rossi.com",
+        "3334445550",
+    );
+    let sent_greeting = Greeting::new(
+        &FriendId::new("2"),
+        "Mario",
+        "Rossi",
+        "mario@rossi.com",
+        "3334445550",
+    );
+    greetings_sender.stub_sent_greetings_result(Err(SendGreetingsError::new(vec![(
+        no_sent_greeting.clone(),
+        SendSingleGreetingError::new("Mailbox full".to_string()),
+    )])));
+
+    let _ = log_greetings_sender.send(vec![no_sent_greeting, sent_greeting]);
+
+    testing_logger::validate(|captured_logs| {
+        assert_eq!(captured_logs.len(), 2);
+        assert_eq!(captured_logs[0].body, "Greeting sent to Mario Rossi");
+        assert_eq!(captured_logs[0].level, Level::Info);
+        assert_eq!(
+            captured_logs[1].body,
+            "Error sending greeting to Mario Rossi - Mailbox full"
+        );
+        assert_eq!(captured_logs[1].level, Level::Error);
+    });
+}
+
+#[test]
+fn tell_apart_the_greetings_sent_to_the_same_friend() {
+    testing_logger::setup();
+    let greetings_sender = Rc::new(GreetingsSenderTestDouble::new());
+    let log_greetings_sender = LogGreetingsSender::new(Rc::clone(&greetings_sender));
+
+    let sent_greeting = Greeting::new(
+        &FriendId::new("1"),
+        "Mario",
+        "Rossi",
+        "mario@rossi.com",
+        "3334445550",
+    );
+    let no_sent_greeting = sent_greeting.clone().with_kind(GreetingKind::Reminder(
+        Reminder::new(NaiveDate::from_ymd_opt(2023, 8, 27).unwrap(), 3)
+            .with_friend("Carla", "Sandri"),
+    ));
+    greetings_sender.stub_sent_greetings_result(Err(SendGreetingsError::new(vec![(
+        no_sent_greeting.clone(),
+        SendSingleGreetingError::new("Mailbox full".to_string()),
+    )])));
+
+    let _ = log_greetings_sender.send(vec![sent_greeting, no_sent_greeting]);
+
+    testing_logger::validate(|captured_logs| {
+        assert_eq!(captured_logs.len(), 2);
+        assert_eq!(captured_logs[0].body, "Greeting sent to Mario Rossi");
+        assert_eq!(captured_logs[0].level, Level::Info);
+        assert_eq!(captured_logs[1].level, Level::Error);
+    });
+}
@@ -1,5 +1,12 @@
 use birthday_greetings_kata_rust::{
-    friends::friend_data::FriendData, greeter_service::Observer, log_observer::LogObserver,
+    friends::{
+        friend_data::FriendData,
+        friends_gateway::{
+            FriendsLoadReport, InvalidFriendRecord, RejectedRecord, ResolvedConflict,
+        },
+    },
+    greeter_service::Observer,
+    log_observer::LogObserver,
 };
 use chrono::NaiveDate;
 use log::Level;
@@ -42,3 +49,63 @@ fn log_friends_celebrating_their_birthdays() {
         assert_eq!(captured_logs[1].level, Level::Info);
     });
 }
+
+#[test]
+fn log_friends_rejected_while_loading() {
+    testing_logger::setup();
+
+    let report = FriendsLoadReport::new(vec![
+        RejectedRecord::new(
+            3,
+            "Germi, Mario, 11/13/1980, mario@germi.com, 3396665559",
+            InvalidFriendRecord::InvalidDate("11/13/1980".to_string()),
+        ),
+        RejectedRecord::new(
+            4,
+            "Doe, Mary, 24/08/1982, mary@doe.com",
+            InvalidFriendRecord::MissingField("phone_number".to_string()),
+        ),
+    ]);
+    let log_observer = LogObserver::default();
+    log_observer.observe_friends_load_report(report);
+
+    testing_logger::validate(|captured_logs| {
+        assert_eq!(captured_logs.len(), 2);
+        assert_eq!(
+            captured_logs[0].body,
+            "Skipped friend at line 3 \"Germi, Mario, 11/13/1980, mario@germi.com, 3396665559\" - invalid date '11/13/1980'"
+        );
+        assert_eq!(captured_logs[0].level, Level::Warn);
+        assert_eq!(
+            captured_logs[1].body,
+            "Skipped friend at line 4 \"Doe, Mary, 24/08/1982, mary@doe.com\" - missing field 'phone_number'"
+        );
+        assert_eq!(captured_logs[1].level, Level::Warn);
+    });
+}
+
+#[test]
+fn log_duplicate_friends_merged_while_loading() {
+    testing_logger::setup();
+
+    let carla = FriendData::new(
+        "Carla",
+        "Sandri",
+        NaiveDate::from_ymd_opt(1980, 6, 12).unwrap(),
+        "carla-sandri@email.com",
+        "3335556667",
+    );
+    let mut report = FriendsLoadReport::default();
+    report.resolved_conflicts.push(ResolvedConflict::new(
+        carla.clone(),
+        vec![carla.clone(), carla],
+    ));
+    let log_observer = LogObserver::default();
+    log_observer.observe_friends_load_report(report);
+
+    testing_logger::validate(|captured_logs| {
+        assert_eq!(captured_logs.len(), 1);
+        assert_eq!(captured_logs[0].body, "Merged 2 duplicates of Carla Sandri");
+        assert_eq!(captured_logs[0].level, Level::Info);
+    });
+}
@@ -0,0 +1,97 @@
+use birthday_greetings_kata_rust::friends::friend_data::FriendId;
+use birthday_greetings_kata_rust::greetings::{
+    greeting::Greeting,
+    greetings_sender::GreetingsSender,
+    smss::{sms::Sms, sms_greetings_sender::SmsGreetingsSender, sms_service::SmsService},
+    templates::{message_templates::MessageTemplates, template::TemplateError},
+};
+use chrono::NaiveDate;
+use std::{cell::RefCell, fs, rc::Rc};
+use tempfile::tempdir;
+
+struct SmsServiceTestDouble {
+    sent_sms: RefCell<Vec<Sms>>,
+}
+
+impl SmsService for SmsServiceTestDouble {
+    fn send(&self, sms: Vec<Sms>) {
+        self.sent_sms.borrow_mut().extend(sms)
+    }
+}
+
+#[test]
+fn render_greetings_with_templates_loaded_from_a_directory() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("text.txt"),
+        "Auguri {name}{if birthdate}, born on {birthdate}{end}!\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("reminder_text.txt"), "Remember {friends}").unwrap();
+    fs::write(
+        dir.path().join("milestone_text.txt"),
+        "{friend_name} is {age}",
+    )
+    .unwrap();
+    fs::write(dir.path().join("notes.md"), "{ignored").unwrap();
+    let sms_service = Rc::new(SmsServiceTestDouble {
+        sent_sms: RefCell::new(Vec::new()),
+    });
+    let mut sms_greetings_sender = SmsGreetingsSender::new(Rc::clone(&sms_service));
+
+    sms_greetings_sender
+        .configure_templates(MessageTemplates::load(dir.path()).unwrap())
+        .unwrap();
+    let _ = sms_greetings_sender.send(vec![Greeting::new(
+        &FriendId::new("franchi-franco"),
+        "Franco",
+        "Franchi",
+        "franco@franchi.com",
+        "3398889990",
+    )
+    .with_birthdate(NaiveDate::from_ymd_opt(1970, 8, 24).unwrap().into())]);
+
+    assert_eq!(
+        sms_service.sent_sms.borrow().clone(),
+        vec![Sms::new(
+            "3334445551",
+            "3398889990",
+            "Auguri Franco, born on 24/08/1970!"
+        )]
+    );
+}
+
+#[test]
+fn reject_templates_with_unknown_placeholders() {
+    let templates = MessageTemplates::from_sources(&[
+        ("text", "Happy birthday, dear {nickname}!"),
+        ("reminder_text", "Remember {friends}"),
+        ("milestone_text", "{friend_name} is {age}"),
+    ])
+    .unwrap();
+    let mut sms_greetings_sender = SmsGreetingsSender::new(Rc::new(SmsServiceTestDouble {
+        sent_sms: RefCell::new(Vec::new()),
+    }));
+
+    assert_eq!(
+        sms_greetings_sender.configure_templates(templates),
+        Err(TemplateError::UnknownPlaceholder {
+            template: "text".to_owned(),
+            placeholder: "nickname".to_owned()
+        })
+    );
+}
+
+#[test]
+fn reject_incomplete_templates() {
+    let templates =
+        MessageTemplates::from_sources(&[("text", "Happy birthday, dear {name}!")]).unwrap();
+    let mut sms_greetings_sender = SmsGreetingsSender::new(Rc::new(SmsServiceTestDouble {
+        sent_sms: RefCell::new(Vec::new()),
+    }));
+
+    assert_eq!(
+        sms_greetings_sender.configure_templates(templates),
+        Err(TemplateError::MissingTemplate("reminder_text".to_owned()))
+    );
+}
@@ -0,0 +1,148 @@
+use birthday_greetings_kata_rust::greetings::emails::{
+    email::Email,
+    inline_image::InlineImage,
+    mime_message::{new_message_id, to_mime_message, MimeMessageError},
+};
+use chrono::{DateTime, FixedOffset};
+
+fn date() -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339("2023-03-24T09:00:00+01:00").unwrap()
+}
+
+fn plain_text_email() -> Email {
+    Email::new(
+        "greeting@service.com",
+        "mary@doe.com",
+        "Happy birthday!",
+        "Happy birthday, dear Mary!",
+    )
+}
+
+fn italian_email() -> Email {
+    Email::new(
+        "auguri@example.it",
+        "niccolo@rossi.it",
+        "Buon 50° compleanno, Niccolò! 🎂 Tanti auguri da tutti gli amici del circolo",
+        "Buon 50° compleanno, Niccolò!\n\
+         Che questo giorno speciale sia pieno di gioia, sorrisi, amici e torta al cioccolato. \n\
+         -- \n\
+         Gli amici = la famiglia che si sceglie",
+    )
+    .with_from_name("Auguri Café")
+    .with_reply_to("amici@example.it")
+}
+
+fn html_email() -> Email {
+    Email::new(
+        "greeting@service.com",
+        "mary@doe.com",
+        "Happy birthday!",
+        "",
+    )
+    .with_from_name("Birthday Service, Inc.")
+    .with_html_body(
+        "<p>Happy birthday, dear Mary!</p><img src=\"cid:birthday-card\" alt=\"A birthday cake\">",
+    )
+    .with_inline_image(
+        InlineImage::new(
+            "birthday-card",
+            "image/png",
+            b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x01\0\0\0\x01\x08\x06\0\0\0\x1f\x15\xc4\x89"
+                .to_vec(),
+        )
+        .unwrap(),
+    )
+}
+
+fn assert_message_matches_fixture(message: Vec<u8>, fixture: &[u8]) {
+    assert_eq!(
+        String::from_utf8(message).unwrap(),
+        String::from_utf8(fixture.to_vec()).unwrap()
+    );
+}
+
+#[test]
+fn serialize_a_plain_text_email() {
+    let message = to_mime_message(&plain_text_email(), &date(), "birthday-1@service.com").unwrap();
+
+    assert_message_matches_fixture(message, include_bytes!("fixtures/plain_text.eml"));
+}
+
+#[test]
+fn serialize_non_ascii_headers_as_encoded_words_and_bodies_as_quoted_printable() {
+    let message = to_mime_message(&italian_email(), &date(), "birthday-2@example.it").unwrap();
+
+    assert_message_matches_fixture(message, include_bytes!("fixtures/non_ascii.eml"));
+}
+
+#[test]
+fn serialize_an_html_email_with_an_inline_image() {
+    let message = to_mime_message(&html_email(), &date(), "birthday-3@service.com").unwrap();
+
+    assert_message_matches_fixture(
+        message,
+        include_bytes!("fixtures/html_with_inline_image.eml"),
+    );
+}
+
+#[test]
+fn keep_every_line_within_the_length_limits() {
+    let email = Email::new(
+        "greeting@service.com",
+        "mary@doe.com",
+        &"Happy birthday to the best friend anyone could ever wish for ".repeat(4),
+        &"Tanti auguri a te, che compi gli anni oggi. ".repeat(10),
+    )
+    .with_html_body(&format!("<p>{}</p>", "ünïcödé ".repeat(40)));
+
+    let message =
+        String::from_utf8(to_mime_message(&email, &date(), "long@service.com").unwrap()).unwrap();
+
+    assert!(message.ends_with("\r\n"));
+    for line in message.split("\r\n") {
+        assert!(line.len() <= 78, "{}", line);
+        assert!(!line.contains('\n') && !line.contains('\r'), "{}", line);
+    }
+}
+
+#[test]
+fn generate_unique_message_ids_in_the_domain_of_the_sender() {
+    let first_id = new_message_id(&plain_text_email(), &date());
+    let second_id = new_message_id(&plain_text_email(), &date());
+
+    assert_ne!(first_id, second_id);
+    assert!(first_id.ends_with("@service.com"));
+}
+
+#[test]
+fn refuse_addresses_that_would_inject_headers() {
+    let email = Email::new(
+        "greeting@service.com",
+        "mary@doe.com\r\nBcc: everyone@example.org",
+        "Happy birthday!",
+        "Happy birthday, dear Mary!",
+    );
+
+    assert_eq!(
+        to_mime_message(&email, &date(), "birthday-4@service.com"),
+        Err(MimeMessageError::InvalidAddress(
+            "mary@doe.com\r\nBcc: everyone@example.org".to_owned()
+        ))
+    );
+    assert_eq!(
+        to_mime_message(
+            &plain_text_email().with_reply_to("friends@example.org\nBcc: x@y.org"),
+            &date(),
+            "birthday-5@service.com"
+        ),
+        Err(MimeMessageError::InvalidAddress(
+            "friends@example.org\nBcc: x@y.org".to_owned()
+        ))
+    );
+    assert_eq!(
+        to_mime_message(&plain_text_email(), &date(), "id>\r\nBcc: x@y.org"),
+        Err(MimeMessageError::InvalidMessageId(
+            "id>\r\nBcc: x@y.org".to_owned()
+        ))
+    );
+}
@@ -0,0 +1,34 @@
+use birthday_greetings_kata_rust::{
+    calendars::{fixed_calendar::FixedCalendar, offset_calendar::OffsetCalendar},
+    greeter_service::Calendar,
+};
+use chrono::NaiveDate;
+use std::rc::Rc;
+
+#[test]
+fn shift_the_date_of_the_wrapped_calendar_back() {
+    let fixed_calendar = Rc::new(FixedCalendar::new(
+        NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+    ));
+
+    let offset_calendar = OffsetCalendar::new(fixed_calendar, -1);
+
+    assert_eq!(
+        offset_calendar.today(),
+        NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+    );
+}
+
+#[test]
+fn shift_the_date_of_the_wrapped_calendar_forward() {
+    let fixed_calendar = Rc::new(FixedCalendar::new(
+        NaiveDate::from_ymd_opt(2023, 12, 30).unwrap(),
+    ));
+
+    let offset_calendar = OffsetCalendar::new(fixed_calendar, 3);
+
+    assert_eq!(
+        offset_calendar.today(),
+        NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()
+    );
+}
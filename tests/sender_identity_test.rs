@@ -0,0 +1,73 @@
+use birthday_greetings_kata_rust::greetings::sender_identity::{
+    EmailIdentity, SenderIdentityError, SmsSenderId,
+};
+
+#[test]
+fn build_an_email_identity_with_display_name_and_reply_to() {
+    let identity = EmailIdentity::new("birthdays@example.org")
+        .and_then(|identity| identity.with_display_name("Example Birthdays"))
+        .and_then(|identity| identity.with_reply_to("friends@example.org"))
+        .unwrap();
+
+    assert_eq!(identity.address(), "birthdays@example.org");
+    assert_eq!(identity.display_name(), Some("Example Birthdays"));
+    assert_eq!(identity.reply_to(), Some("friends@example.org"));
+}
+
+#[test]
+fn reject_invalid_email_identities() {
+    for address in [
+        "",
+        "birthdays",
+        "birthdays@",
+        "@example.org",
+        "birthdays@example",
+        "birth days@example.org",
+        "birthdays@@example.org",
+        "birthdays..team@example.org",
+        "birthdays@-example.org",
+    ] {
+        assert_eq!(
+            EmailIdentity::new(address),
+            Err(SenderIdentityError::InvalidAddress(address.to_owned())),
+            "{}",
+            address
+        );
+    }
+    assert_eq!(
+        EmailIdentity::default().with_display_name("Birthdays\r\nBcc: all@example.org"),
+        Err(SenderIdentityError::InvalidDisplayName(
+            "Birthdays\r\nBcc: all@example.org".to_owned()
+        ))
+    );
+    assert_eq!(
+        EmailIdentity::default().with_reply_to("friends"),
+        Err(SenderIdentityError::InvalidAddress("friends".to_owned()))
+    );
+}
+
+#[test]
+fn accept_phone_numbers_short_codes_and_alphanumeric_sender_ids() {
+    assert_eq!(
+        SmsSenderId::phone_number("+41791234567").map(|id| id.as_str().to_owned()),
+        Ok("+41791234567".to_owned())
+    );
+    assert!(SmsSenderId::phone_number("4455").is_ok());
+    assert!(SmsSenderId::alphanumeric("Birthdays").is_ok());
+}
+
+#[test]
+fn reject_invalid_sms_sender_ids() {
+    for sender_id in ["", "12", "333-444-5551", "+1234567890123456"] {
+        assert_eq!(
+            SmsSenderId::phone_number(sender_id),
+            Err(SenderIdentityError::InvalidSenderId(sender_id.to_owned()))
+        );
+    }
+    for sender_id in ["", "12345", "BirthdaysTeam", "Bday!"] {
+        assert_eq!(
+            SmsSenderId::alphanumeric(sender_id),
+            Err(SenderIdentityError::InvalidSenderId(sender_id.to_owned()))
+        );
+    }
+}
@@ -1,8 +1,11 @@
+use birthday_greetings_kata_rust::friends::friend_data::FriendId;
 use birthday_greetings_kata_rust::greetings::{
-    greeting::Greeting,
+    greeting::{Greeting, GreetingKind, Reminder},
     greetings_sender::GreetingsSender,
+    sender_identity::SmsSenderId,
     smss::{sms::Sms, sms_greetings_sender::SmsGreetingsSender, sms_service::SmsService},
 };
+use chrono::NaiveDate;
 use std::{cell::RefCell, rc::Rc};
 
 struct SmsServiceTestDouble {
@@ -33,8 +36,20 @@ fn send_greetings_as_sms() {
     let sms_greetings_sender = SmsGreetingsSender::new(Rc::clone(&sms_service_test_double));
 
     let greetings = vec![
-        Greeting::new("Franco", "Franchi", "franco@franchi.com", "3398889990"),
-        Greeting::new("Mary", "Doe", "mary@doe.com", "3396665559"),
+        Greeting::new(
+            &FriendId::new("franchi-franco"),
+            "Franco",
+            "Franchi",
+            "franco@franchi.com",
+            "3398889990",
+        ),
+        Greeting::new(
+            &FriendId::new("doe-mary"),
+            "Mary",
+            "Doe",
+            "mary@doe.com",
+            "3396665559",
+        ),
     ];
     let _ = sms_greetings_sender.send(greetings);
 
@@ -48,6 +63,126 @@ fn send_greetings_as_sms() {
     )
 }
 
+#[test]
+fn send_belated_greetings_as_sms() {
+    let sms_service_test_double = Rc::new(SmsServiceTestDouble::new());
+    let sms_greetings_sender = SmsGreetingsSender::new(Rc::clone(&sms_service_test_double));
+
+    let greetings = vec![Greeting::new(
+        &FriendId::new("franchi-franco"),
+        "Franco",
+        "Franchi",
+        "franco@franchi.com",
+        "3398889990",
+    )
+    .with_kind(GreetingKind::BelatedBirthday)];
+    let _ = sms_greetings_sender.send(greetings);
+
+    let sms = sms_service_test_double.spied_sms_to_send();
+    assert_eq!(
+        sms,
+        vec![Sms::new(
+            "3334445551",
+            "3398889990",
+            "Happy belated birthday, dear Franco!"
+        )]
+    )
+}
+
+#[test]
+fn send_reminders_as_sms() {
+    let sms_service_test_double = Rc::new(SmsServiceTestDouble::new());
+    let sms_greetings_sender = SmsGreetingsSender::new(Rc::clone(&sms_service_test_double));
+
+    let greetings = vec![Greeting::new(
+        &FriendId::new("doe-mary"),
+        "Mary",
+        "Doe",
+        "mary@doe.com",
+        "3396665559",
+    )
+    .with_kind(GreetingKind::Reminder(
+        Reminder::new(NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(), 3)
+            .with_friend("Franco", "Franchi"),
+    ))];
+    let _ = sms_greetings_sender.send(greetings);
+
+    let sms = sms_service_test_double.spied_sms_to_send();
+    assert_eq!(
+        sms,
+        vec![Sms::new(
+            "3334445551",
+            "3396665559",
+            "Reminder: Franco Franchi's birthday is on 24/08!"
+        )]
+    )
+}
+
+#[test]
+fn send_milestone_greetings_as_sms_only_when_the_age_is_a_milestone() {
+    let sms_service_test_double = Rc::new(SmsServiceTestDouble::new());
+    let sms_greetings_sender = SmsGreetingsSender::new(Rc::clone(&sms_service_test_double));
+
+    let greetings = vec![
+        Greeting::new(
+            &FriendId::new("franchi-franco"),
+            "Franco",
+            "Franchi",
+            "franco@franchi.com",
+            "3398889990",
+        )
+        .with_age(112)
+        .with_milestone(true),
+        Greeting::new(
+            &FriendId::new("doe-mary"),
+            "Mary",
+            "Doe",
+            "mary@doe.com",
+            "3396665559",
+        )
+        .with_age(41),
+    ];
+    let _ = sms_greetings_sender.send(greetings);
+
+    let sms = sms_service_test_double.spied_sms_to_send();
+    assert_eq!(
+        sms,
+        vec![
+            Sms::new(
+                "3334445551",
+                "3398889990",
+                "Happy 112th birthday, dear Franco!"
+            ),
+            Sms::new("3334445551", "3396665559", "Happy birthday, dear Mary!")
+        ]
+    )
+}
+
+#[test]
+fn send_greetings_from_the_configured_sender_id() {
+    let sms_service_test_double = Rc::new(SmsServiceTestDouble::new());
+    let mut sms_greetings_sender = SmsGreetingsSender::new(Rc::clone(&sms_service_test_double));
+    sms_greetings_sender.configure_sender_id(SmsSenderId::alphanumeric("Birthdays").unwrap());
+
+    let _ = sms_greetings_sender.send(vec![Greeting::new(
+        &FriendId::new("doe-mary"),
+        "Mary",
+        "Doe",
+        "mary@doe.com",
+        "3396665559",
+    )]);
+
+    let sms = sms_service_test_double.spied_sms_to_send();
+    assert_eq!(
+        sms,
+        vec![Sms::new(
+            "Birthdays",
+            "3396665559",
+            "Happy birthday, dear Mary!"
+        )]
+    )
+}
+
 #[test]
 fn does_not_send_anything_when_asked_to_send_no_greeting() {
     let sms_service_test_double = Rc::new(SmsServiceTestDouble::new());
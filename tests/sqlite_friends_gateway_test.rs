@@ -0,0 +1,271 @@
+use birthday_greetings_kata_rust::friends::birthdate::Birthdate;
+use birthday_greetings_kata_rust::friends::friend_data::{FriendData, FriendId, Locale};
+use birthday_greetings_kata_rust::friends::friends_gateway::{
+    FriendsGateway, FriendsGatewayError, InvalidFriendRecord,
+};
+use birthday_greetings_kata_rust::friends::sqlite_friends_gateway::SqliteFriendsGateway;
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use rusqlite::Connection;
+use std::time::{Duration, SystemTime};
+use tempfile::NamedTempFile;
+
+fn insert_friends(database: &NamedTempFile, friends: &[(&str, &str, Option<i32>, u32, u32)]) {
+    let connection = Connection::open(database.path()).unwrap();
+    for (name, surname, birth_year, birth_month, birth_day) in friends {
+        connection
+            .execute(
+                "INSERT INTO friends (name, surname, birth_year, birth_month, birth_day, email, phone_number)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                rusqlite::params![
+                    name,
+                    surname,
+                    birth_year,
+                    birth_month,
+                    birth_day,
+                    format!("{}@{}.com", name, surname).to_lowercase(),
+                    "3398889990"
+                ],
+            )
+            .unwrap();
+    }
+}
+
+#[test]
+fn migrate_a_new_database_to_the_latest_schema_version() {
+    let database = NamedTempFile::new().unwrap();
+
+    let sqlite_friends_gateway = SqliteFriendsGateway::open(database.path()).unwrap();
+
+    assert_eq!(sqlite_friends_gateway.schema_version(), Ok(4));
+    assert_eq!(sqlite_friends_gateway.get_friends(), Ok(Vec::new()));
+}
+
+#[test]
+fn keep_the_data_of_an_already_migrated_database() {
+    let database = NamedTempFile::new().unwrap();
+    SqliteFriendsGateway::open(database.path()).unwrap();
+    insert_friends(&database, &[("Franca", "Franchi", Some(1970), 8, 24)]);
+
+    let sqlite_friends_gateway = SqliteFriendsGateway::open(database.path()).unwrap();
+
+    assert_eq!(sqlite_friends_gateway.schema_version(), Ok(4));
+    assert_eq!(sqlite_friends_gateway.get_friends().unwrap().len(), 1);
+}
+
+#[test]
+fn track_when_friends_were_last_modified() {
+    let database = NamedTempFile::new().unwrap();
+    let sqlite_friends_gateway = SqliteFriendsGateway::open(database.path()).unwrap();
+    let never_modified = sqlite_friends_gateway.last_modified();
+    insert_friends(&database, &[("Franca", "Franchi", Some(1970), 8, 24)]);
+    let connection = Connection::open(database.path()).unwrap();
+    connection
+        .execute("UPDATE friends SET modified_at = 1692835200000", [])
+        .unwrap();
+    let explicitly_set = sqlite_friends_gateway.last_modified();
+    connection
+        .execute("UPDATE friends SET phone_number = '3391112223'", [])
+        .unwrap();
+    let updated = sqlite_friends_gateway.last_modified().unwrap();
+
+    assert_eq!(never_modified, None);
+    assert_eq!(
+        explicitly_set,
+        Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1692835200))
+    );
+    assert!(updated.elapsed().unwrap() < Duration::from_secs(60));
+}
+
+#[test]
+fn refuse_databases_with_an_unknown_schema_version() {
+    let database = NamedTempFile::new().unwrap();
+    Connection::open(database.path())
+        .unwrap()
+        .execute_batch("PRAGMA user_version = 99")
+        .unwrap();
+
+    let sqlite_friends_gateway = SqliteFriendsGateway::open(database.path());
+
+    assert!(matches!(
+        sqlite_friends_gateway,
+        Err(FriendsGatewayError::Database(_))
+    ));
+}
+
+#[test]
+fn get_friends_from_sqlite_database() {
+    let database = NamedTempFile::new().unwrap();
+    let sqlite_friends_gateway = SqliteFriendsGateway::open(database.path()).unwrap();
+    insert_friends(
+        &database,
+        &[
+            ("Franca", "Franchi", Some(1970), 8, 24),
+            ("Mario", "Germi", None, 12, 11),
+        ],
+    );
+
+    let friends = sqlite_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Ok(vec![
+            FriendData::new(
+                "Franca",
+                "Franchi",
+                NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+                "franca@franchi.com",
+                "3398889990"
+            )
+            .with_id(FriendId::new("1")),
+            FriendData::new(
+                "Mario",
+                "Germi",
+                Birthdate::from_md_opt(12, 11).unwrap(),
+                "mario@germi.com",
+                "3398889990"
+            )
+            .with_id(FriendId::new("2"))
+        ])
+    );
+}
+
+#[test]
+fn get_only_friends_born_on_the_given_days() {
+    let database = NamedTempFile::new().unwrap();
+    let sqlite_friends_gateway = SqliteFriendsGateway::open(database.path()).unwrap();
+    insert_friends(
+        &database,
+        &[
+            ("Franca", "Franchi", Some(1970), 2, 28),
+            ("Mario", "Germi", Some(1980), 12, 11),
+            ("Mary", "Doe", Some(1984), 2, 29),
+        ],
+    );
+
+    let (friends, _) = sqlite_friends_gateway
+        .get_friends_born_on(&[(2, 28), (2, 29)])
+        .unwrap();
+
+    assert_eq!(
+        friends,
+        vec![
+            FriendData::new(
+                "Franca",
+                "Franchi",
+                NaiveDate::from_ymd_opt(1970, 2, 28).unwrap(),
+                "franca@franchi.com",
+                "3398889990"
+            )
+            .with_id(FriendId::new("1")),
+            FriendData::new(
+                "Mary",
+                "Doe",
+                NaiveDate::from_ymd_opt(1984, 2, 29).unwrap(),
+                "mary@doe.com",
+                "3398889990"
+            )
+            .with_id(FriendId::new("3"))
+        ]
+    );
+}
+
+#[test]
+fn fail_to_get_friends_with_an_invalid_stored_birthdate() {
+    let database = NamedTempFile::new().unwrap();
+    let sqlite_friends_gateway = SqliteFriendsGateway::open(database.path()).unwrap();
+    insert_friends(&database, &[("Franca", "Franchi", Some(1970), 2, 31)]);
+
+    let friends = sqlite_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidRow {
+            id: 1,
+            error: InvalidFriendRecord::InvalidDate("1970-02-31".to_string())
+        })
+    );
+}
+
+#[test]
+fn migrate_a_database_at_the_first_schema_version_keeping_its_data() {
+    let database = NamedTempFile::new().unwrap();
+    Connection::open(database.path())
+        .unwrap()
+        .execute_batch(
+            "CREATE TABLE friends (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                surname TEXT NOT NULL,
+                birth_year INTEGER,
+                birth_month INTEGER NOT NULL,
+                birth_day INTEGER NOT NULL,
+                email TEXT NOT NULL,
+                phone_number TEXT NOT NULL
+            );
+            INSERT INTO friends VALUES (1, 'Franca', 'Franchi', 1970, 8, 24, 'franca@franchi.com', '3398889990');
+            PRAGMA user_version = 1;",
+        )
+        .unwrap();
+
+    let sqlite_friends_gateway = SqliteFriendsGateway::open(database.path()).unwrap();
+
+    assert_eq!(sqlite_friends_gateway.schema_version(), Ok(4));
+    assert_eq!(
+        sqlite_friends_gateway.get_friends(),
+        Ok(vec![FriendData::new(
+            "Franca",
+            "Franchi",
+            NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+            "franca@franchi.com",
+            "3398889990"
+        )
+        .with_id(FriendId::new("1"))])
+    );
+}
+
+#[test]
+fn get_friends_with_their_time_zone() {
+    let database = NamedTempFile::new().unwrap();
+    let sqlite_friends_gateway = SqliteFriendsGateway::open(database.path()).unwrap();
+    insert_friends(
+        &database,
+        &[
+            ("Franca", "Franchi", Some(1970), 8, 24),
+            ("Mario", "Germi", Some(1980), 12, 11),
+        ],
+    );
+    Connection::open(database.path())
+        .unwrap()
+        .execute_batch(
+            "UPDATE friends SET time_zone = 'Asia/Tokyo' WHERE id = 1;
+             UPDATE friends SET time_zone = 'Mars/Olympus_Mons' WHERE id = 2;",
+        )
+        .unwrap();
+
+    let friends = sqlite_friends_gateway.get_friends_born_on(&[(8, 24)]);
+
+    assert_eq!(friends.unwrap().0[0].time_zone, Some(Tz::Asia__Tokyo));
+    assert_eq!(
+        sqlite_friends_gateway.get_friends(),
+        Err(FriendsGatewayError::InvalidRow {
+            id: 2,
+            error: InvalidFriendRecord::InvalidTimeZone("Mars/Olympus_Mons".to_string())
+        })
+    );
+}
+
+#[test]
+fn get_friends_with_their_locale() {
+    let database = NamedTempFile::new().unwrap();
+    let sqlite_friends_gateway = SqliteFriendsGateway::open(database.path()).unwrap();
+    insert_friends(&database, &[("Franca", "Franchi", Some(1970), 8, 24)]);
+    Connection::open(database.path())
+        .unwrap()
+        .execute_batch("UPDATE friends SET locale = 'it-CH' WHERE id = 1;")
+        .unwrap();
+
+    let friends = sqlite_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(friends[0].locale, Locale::parse("it-CH"));
+}
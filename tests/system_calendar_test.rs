@@ -0,0 +1,60 @@
+use birthday_greetings_kata_rust::{
+    calendars::system_calendar::{SystemCalendar, UnknownTimeZoneError},
+    greeter_service::Calendar,
+};
+use chrono::{NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+
+#[test]
+fn derive_today_from_the_system_clock_in_utc_by_default() {
+    let system_calendar = SystemCalendar::default();
+
+    let before = Utc::now().date_naive();
+    let today = system_calendar.today();
+    let after = Utc::now().date_naive();
+
+    assert!(today == before || today == after);
+}
+
+#[test]
+fn derive_the_date_in_the_configured_time_zone() {
+    let instant = Utc.with_ymd_and_hms(2023, 8, 23, 23, 30, 0).unwrap();
+
+    assert_eq!(
+        SystemCalendar::new(Tz::Europe__Rome).date_at(instant),
+        NaiveDate::from_ymd_opt(2023, 8, 24).unwrap()
+    );
+    assert_eq!(
+        SystemCalendar::new(Tz::America__New_York).date_at(instant),
+        NaiveDate::from_ymd_opt(2023, 8, 23).unwrap()
+    );
+}
+
+#[test]
+fn follow_daylight_saving_time_transitions() {
+    let system_calendar = SystemCalendar::from_time_zone_name("Europe/Rome").unwrap();
+
+    let winter_instant = Utc.with_ymd_and_hms(2023, 1, 10, 23, 30, 0).unwrap();
+    let summer_instant = Utc.with_ymd_and_hms(2023, 7, 10, 22, 30, 0).unwrap();
+
+    assert_eq!(
+        system_calendar.date_at(winter_instant),
+        NaiveDate::from_ymd_opt(2023, 1, 11).unwrap()
+    );
+    assert_eq!(
+        system_calendar.date_at(summer_instant),
+        NaiveDate::from_ymd_opt(2023, 7, 11).unwrap()
+    );
+}
+
+#[test]
+fn fail_to_build_with_an_unknown_time_zone_name() {
+    let system_calendar = SystemCalendar::from_time_zone_name("Europe/Atlantis");
+
+    assert_eq!(
+        system_calendar.err(),
+        Some(UnknownTimeZoneError {
+            time_zone: "Europe/Atlantis".to_owned()
+        })
+    );
+}
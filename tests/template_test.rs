@@ -0,0 +1,75 @@
+use birthday_greetings_kata_rust::greetings::templates::template::{Template, TemplateError};
+use std::collections::HashMap;
+
+#[test]
+fn render_placeholders_with_filters() {
+    let template = Template::parse(
+        "body",
+        "Happy {age|ordinal} birthday, dear {name|capitalize} {surname|upper}! {{not a placeholder}}",
+    )
+    .unwrap();
+
+    let rendered = template.render(&HashMap::from([
+        ("name", "franco".to_owned()),
+        ("surname", "Franchi".to_owned()),
+        ("age", "42".to_owned()),
+    ]));
+
+    assert_eq!(
+        rendered,
+        "Happy 42nd birthday, dear Franco FRANCHI! {not a placeholder}"
+    );
+}
+
+#[test]
+fn render_conditionals() {
+    let template = Template::parse(
+        "body",
+        "{if milestone}Happy {age|ordinal}{else}Happy{end} birthday{if not belated}!{else}, albeit late!{end}",
+    )
+    .unwrap();
+
+    let on_time = template.render(&HashMap::from([
+        ("milestone", "true".to_owned()),
+        ("age", "40".to_owned()),
+    ]));
+    let belated = template.render(&HashMap::from([
+        ("milestone", "".to_owned()),
+        ("belated", "true".to_owned()),
+    ]));
+
+    assert_eq!(on_time, "Happy 40th birthday!");
+    assert_eq!(belated, "Happy birthday, albeit late!");
+}
+
+#[test]
+fn list_the_placeholders_used() {
+    let template = Template::parse("body", "{if milestone}{age}{end} {name} {name|upper}").unwrap();
+
+    assert_eq!(template.placeholders(), vec!["age", "milestone", "name"]);
+}
+
+#[test]
+fn reject_invalid_templates() {
+    assert_eq!(
+        Template::parse("body", "Happy birthday, dear {name|shout}!"),
+        Err(TemplateError::UnknownFilter {
+            template: "body".to_owned(),
+            filter: "shout".to_owned()
+        })
+    );
+    assert_eq!(
+        Template::parse("body", "Happy birthday, dear {name"),
+        Err(TemplateError::InvalidSyntax {
+            template: "body".to_owned(),
+            message: "unclosed '{'".to_owned()
+        })
+    );
+    assert_eq!(
+        Template::parse("body", "{if milestone}Happy birthday"),
+        Err(TemplateError::InvalidSyntax {
+            template: "body".to_owned(),
+            message: "missing 'end'".to_owned()
+        })
+    );
+}
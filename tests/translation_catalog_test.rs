@@ -0,0 +1,186 @@
+use birthday_greetings_kata_rust::friends::friend_data::{FriendId, Locale};
+use birthday_greetings_kata_rust::greetings::{
+    emails::{email::Email, email_greetings_sender::EmailGreetingsSender, mailer::Mailer},
+    greeting::Greeting,
+    greetings_sender::GreetingsSender,
+    smss::{sms::Sms, sms_greetings_sender::SmsGreetingsSender, sms_service::SmsService},
+    templates::{
+        message_templates::MessageTemplates, template::TemplateError,
+        translation_catalog::TranslationCatalog,
+    },
+};
+use chrono::NaiveDate;
+use std::{cell::RefCell, fs, path::Path, rc::Rc};
+use tempfile::tempdir;
+
+struct SmsServiceTestDouble {
+    sent_sms: RefCell<Vec<Sms>>,
+}
+
+impl SmsService for SmsServiceTestDouble {
+    fn send(&self, sms: Vec<Sms>) {
+        self.sent_sms.borrow_mut().extend(sms)
+    }
+}
+
+struct MailerTestDouble {
+    sent_emails: RefCell<Vec<Email>>,
+}
+
+impl Mailer for MailerTestDouble {
+    fn send(&self, emails: Vec<Email>) {
+        self.sent_emails.borrow_mut().extend(emails)
+    }
+}
+
+fn write_templates(directory: &Path, templates: &[(&str, &str)]) {
+    fs::create_dir_all(directory).unwrap();
+    for (name, source) in templates {
+        fs::write(directory.join(format!("{}.txt", name)), source).unwrap();
+    }
+}
+
+fn greeting_in(locale: &str) -> Greeting {
+    Greeting::new(
+        &FriendId::new("franchi-franco"),
+        "Franco",
+        "Franchi",
+        "franco@franchi.com",
+        "3398889990",
+    )
+    .with_birthdate(NaiveDate::from_ymd_opt(1970, 8, 24).unwrap().into())
+    .with_locale(Locale::parse(locale))
+}
+
+#[test]
+fn fall_back_from_region_to_language_to_default_templates() {
+    let dir = tempdir().unwrap();
+    write_templates(
+        dir.path(),
+        &[
+            ("text", "Happy birthday, {name}!"),
+            ("reminder_text", "Remember {friends}"),
+            ("milestone_text", "{friend_name} is {age}"),
+        ],
+    );
+    write_templates(
+        &dir.path().join("it"),
+        &[("text", "Auguri {name}, nato il {birthdate}!")],
+    );
+    write_templates(&dir.path().join("it-CH"), &[("reminder_text", "Ricorda")]);
+    let sms_service = Rc::new(SmsServiceTestDouble {
+        sent_sms: RefCell::new(Vec::new()),
+    });
+    let mut sms_greetings_sender = SmsGreetingsSender::new(Rc::clone(&sms_service));
+
+    sms_greetings_sender
+        .configure_translations(TranslationCatalog::load(dir.path()).unwrap())
+        .unwrap();
+    let _ = sms_greetings_sender.send(vec![
+        greeting_in("it-CH"),
+        greeting_in("it"),
+        greeting_in("fr"),
+    ]);
+
+    assert_eq!(
+        sms_service
+            .sent_sms
+            .borrow()
+            .iter()
+            .map(|sms| sms.text_body.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "Auguri Franco, nato il 24 agosto 1970!",
+            "Auguri Franco, nato il 24 agosto 1970!",
+            "Happy birthday, Franco!"
+        ]
+    );
+}
+
+#[test]
+fn reject_translations_with_unknown_placeholders_or_locales() {
+    let dir = tempdir().unwrap();
+    write_templates(
+        dir.path(),
+        &[
+            ("text", "Happy birthday, {name}!"),
+            ("reminder_text", "Remember {friends}"),
+            ("milestone_text", "{friend_name} is {age}"),
+        ],
+    );
+    write_templates(&dir.path().join("de"), &[("text", "Hallo {vorname}!")]);
+    let mut sms_greetings_sender = SmsGreetingsSender::new(Rc::new(SmsServiceTestDouble {
+        sent_sms: RefCell::new(Vec::new()),
+    }));
+
+    assert_eq!(
+        sms_greetings_sender.configure_translations(TranslationCatalog::load(dir.path()).unwrap()),
+        Err(TemplateError::UnknownPlaceholder {
+            template: "text".to_owned(),
+            placeholder: "vorname".to_owned()
+        })
+    );
+
+    write_templates(&dir.path().join("klingon"), &[]);
+    assert_eq!(
+        TranslationCatalog::load(dir.path()),
+        Err(TemplateError::InvalidLocale("klingon".to_owned()))
+    );
+}
+
+#[test]
+fn write_each_email_in_the_first_language_with_all_its_messages() {
+    let templates = |sources| MessageTemplates::from_sources(sources).unwrap();
+    let translations = TranslationCatalog::new(templates(&[
+        ("subject", "Happy birthday!"),
+        ("body", "Happy birthday, {name}!"),
+        ("reminder_subject", "Reminder"),
+        ("reminder_body", "Remember {friends}"),
+        ("milestone_subject", "Milestone"),
+        ("milestone_body", "{friend_name} is {age}"),
+    ]))
+    .with_translation(
+        Locale::parse("it").unwrap(),
+        templates(&[
+            ("subject", "Buon compleanno!"),
+            ("html", "<p>Auguri {name}!</p>"),
+            ("reminder_subject", "Promemoria"),
+            ("reminder_html", "<p>Ricorda {friends}</p>"),
+            ("milestone_subject", "Traguardo"),
+            ("milestone_html", "<p>{friend_name} compie {age}</p>"),
+        ]),
+    )
+    .with_translation(
+        Locale::parse("de").unwrap(),
+        templates(&[("body", "Alles Gute, {name}!")]),
+    );
+    let mailer = Rc::new(MailerTestDouble {
+        sent_emails: RefCell::new(Vec::new()),
+    });
+    let mut email_greetings_sender = EmailGreetingsSender::new(Rc::clone(&mailer));
+
+    email_greetings_sender
+        .configure_translations(translations)
+        .unwrap();
+    let _ = email_greetings_sender.send(vec![greeting_in("it-CH"), greeting_in("de")]);
+
+    assert_eq!(
+        *mailer.sent_emails.borrow(),
+        vec![
+            Email::new(
+                "greeting@service.com",
+                "franco@franchi.com",
+                "Buon compleanno!",
+                ""
+            )
+            .with_html_body("<p>Auguri Franco!</p>"),
+            Email::new(
+                "greeting@service.com",
+                "franco@franchi.com",
+                "Happy birthday!",
+                "Happy birthday, Franco!"
+            )
+        ]
+    );
+    assert_eq!(mailer.sent_emails.borrow()[0].text_body, "Auguri Franco!");
+}
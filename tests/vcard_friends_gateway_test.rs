@@ -0,0 +1,417 @@
+use birthday_greetings_kata_rust::friends::birthdate::Birthdate;
+use birthday_greetings_kata_rust::friends::birthdate_parser::{BirthdateParser, DateFormat};
+use birthday_greetings_kata_rust::friends::friend_data::{FriendData, FriendId, Locale};
+use birthday_greetings_kata_rust::friends::friends_gateway::{
+    FriendsGateway, FriendsGatewayError, InvalidFriendRecord, MatchedDateFormat, RejectedRecord,
+};
+use birthday_greetings_kata_rust::friends::vcard_friends_gateway::VCardFriendsGateway;
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use std::io::{Cursor, Result, Write};
+use tempfile::NamedTempFile;
+
+#[test]
+fn get_friends_from_vcard_file_with_multiple_contacts() -> Result<()> {
+    let mut temp_file = NamedTempFile::new()?;
+    temp_file.write_all(
+        b"BEGIN:VCARD\r\n\
+          VERSION:3.0\r\n\
+          N:Franchi;Franca;;;\r\n\
+          FN:Franca Franchi\r\n\
+          BDAY:1970-08-24\r\n\
+          EMAIL;TYPE=INTERNET:franca@franchi.com\r\n\
+          TEL;TYPE=CELL:3398889990\r\n\
+          END:VCARD\r\n\
+          BEGIN:VCARD\r\n\
+          VERSION:4.0\r\n\
+          N:Germi;Mario;;;\r\n\
+          BDAY:19801211\r\n\
+          EMAIL:mario@germi.com\r\n\
+          TEL;VALUE=uri;TYPE=\"voice,cell\":tel:3396665559\r\n\
+          END:VCARD\r\n",
+    )?;
+    let vcard_friends_gateway = VCardFriendsGateway::new(temp_file.reopen()?);
+
+    let friends = vcard_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Ok(vec![
+            FriendData::new(
+                "Franca",
+                "Franchi",
+                NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+                "franca@franchi.com",
+                "3398889990"
+            ),
+            FriendData::new(
+                "Mario",
+                "Germi",
+                NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+                "mario@germi.com",
+                "3396665559"
+            )
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn get_friends_with_birthdays_without_year() {
+    let vcard_friends_gateway = VCardFriendsGateway::from_reader(Cursor::new(
+        "BEGIN:VCARD\n\
+         VERSION:4.0\n\
+         N:Franchi;Franca;;;\n\
+         BDAY:--0824\n\
+         EMAIL:franca@franchi.com\n\
+         TEL:3398889990\n\
+         END:VCARD\n\
+         BEGIN:VCARD\n\
+         VERSION:3.0\n\
+         N:Germi;Mario;;;\n\
+         BDAY:--12-11\n\
+         EMAIL:mario@germi.com\n\
+         TEL:3396665559\n\
+         END:VCARD\n",
+    ));
+
+    let friends = vcard_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Ok(vec![
+            FriendData::new(
+                "Franca",
+                "Franchi",
+                Birthdate::from_md_opt(8, 24).unwrap(),
+                "franca@franchi.com",
+                "3398889990"
+            ),
+            FriendData::new(
+                "Mario",
+                "Germi",
+                Birthdate::from_md_opt(12, 11).unwrap(),
+                "mario@germi.com",
+                "3396665559"
+            )
+        ])
+    );
+}
+
+#[test]
+fn get_friends_from_folded_lines_and_escaped_values() {
+    let vcard_friends_gateway = VCardFriendsGateway::from_reader(Cursor::new(
+        "BEGIN:VCARD\r\n\
+         VERSION:3.0\r\n\
+         N:De Luca\\, Jr.;Ma\r\n \
+         rio;;;\r\n\
+         BDAY:1980-12-11\r\n\
+         item1.EMAIL:mario.deluca@very-long-domain-name-for-a-\r\n\tcompany.com\r\n\
+         TEL:3396665559\r\n\
+         END:VCARD\r\n",
+    ));
+
+    let friends = vcard_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Ok(vec![FriendData::new(
+            "Mario",
+            "De Luca, Jr.",
+            NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+            "mario.deluca@very-long-domain-name-for-a-company.com",
+            "3396665559"
+        )])
+    );
+}
+
+#[test]
+fn use_the_formatted_name_when_the_structured_one_is_missing() {
+    let vcard_friends_gateway = VCardFriendsGateway::from_reader(Cursor::new(
+        "BEGIN:VCARD\n\
+         VERSION:4.0\n\
+         FN:Mary Ann Doe\n\
+         BDAY:1982-08-24\n\
+         EMAIL:mary@doe.com\n\
+         TEL:3336667770\n\
+         END:VCARD\n",
+    ));
+
+    let friends = vcard_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Ok(vec![FriendData::new(
+            "Mary Ann",
+            "Doe",
+            NaiveDate::from_ymd_opt(1982, 8, 24).unwrap(),
+            "mary@doe.com",
+            "3336667770"
+        )])
+    );
+}
+
+#[test]
+fn skip_contacts_without_birthday() {
+    let vcard_friends_gateway = VCardFriendsGateway::from_reader(Cursor::new(
+        "BEGIN:VCARD\n\
+         VERSION:3.0\n\
+         N:Franchi;Franca;;;\n\
+         EMAIL:franca@franchi.com\n\
+         END:VCARD\n",
+    ));
+
+    let friends = vcard_friends_gateway.get_friends();
+
+    assert_eq!(friends, Ok(Vec::new()));
+}
+
+#[test]
+fn fail_to_get_friends_with_an_invalid_birthday() {
+    let vcard_friends_gateway = VCardFriendsGateway::from_reader(Cursor::new(
+        "BEGIN:VCARD\n\
+         VERSION:3.0\n\
+         N:Franchi;Franca;;;\n\
+         BDAY:24th of August\n\
+         EMAIL:franca@franchi.com\n\
+         TEL:3398889990\n\
+         END:VCARD\n",
+    ));
+
+    let friends = vcard_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidRecord {
+            line: 4,
+            error: InvalidFriendRecord::InvalidDate("24th of August".to_string())
+        })
+    );
+}
+
+#[test]
+fn fail_to_get_friends_from_contacts_without_a_name() {
+    let vcard_friends_gateway = VCardFriendsGateway::from_reader(Cursor::new(
+        "BEGIN:VCARD\n\
+         VERSION:3.0\n\
+         BDAY:1970-08-24\n\
+         EMAIL:franca@franchi.com\n\
+         TEL:3398889990\n\
+         END:VCARD\n",
+    ));
+
+    let friends = vcard_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidRecord {
+            line: 1,
+            error: InvalidFriendRecord::MissingField("N".to_string())
+        })
+    );
+}
+
+#[test]
+fn fail_to_get_friends_from_unterminated_contacts() {
+    let vcard_friends_gateway = VCardFriendsGateway::from_reader(Cursor::new(
+        "BEGIN:VCARD\n\
+         VERSION:3.0\n\
+         N:Franchi;Franca;;;\n\
+         BDAY:1970-08-24\n",
+    ));
+
+    let friends = vcard_friends_gateway.get_friends();
+
+    assert_eq!(
+        friends,
+        Err(FriendsGatewayError::InvalidRecord {
+            line: 1,
+            error: InvalidFriendRecord::MalformedVCard("missing END:VCARD".to_string())
+        })
+    );
+}
+
+#[test]
+fn identify_friends_by_their_contact_uid() {
+    let vcard_friends_gateway = VCardFriendsGateway::from_reader(Cursor::new(
+        "BEGIN:VCARD\n\
+         VERSION:4.0\n\
+         UID:urn:uuid:4fbe8971-0bc3-424c-9c26-36c3e1eff6b1\n\
+         N:Franchi;Franca;;;\n\
+         BDAY:1970-08-24\n\
+         EMAIL:franca@franchi.com\n\
+         TEL:3398889990\n\
+         END:VCARD\n",
+    ));
+
+    let friends = vcard_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends[0].id,
+        FriendId::new("urn:uuid:4fbe8971-0bc3-424c-9c26-36c3e1eff6b1")
+    );
+}
+
+#[test]
+fn get_friends_with_their_time_zone() {
+    let vcard_friends_gateway = VCardFriendsGateway::from_reader(Cursor::new(
+        "BEGIN:VCARD\n\
+         VERSION:4.0\n\
+         N:Franchi;Franca;;;\n\
+         BDAY:1970-08-24\n\
+         EMAIL:franca@franchi.com\n\
+         TEL:3398889990\n\
+         TZ:Asia/Tokyo\n\
+         END:VCARD\n",
+    ));
+
+    let friends = vcard_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(friends[0].time_zone, Some(Tz::Asia__Tokyo));
+}
+
+#[test]
+fn get_friends_with_their_preferred_language() {
+    let vcard_friends_gateway = VCardFriendsGateway::from_reader(Cursor::new(
+        "BEGIN:VCARD\n\
+         VERSION:4.0\n\
+         N:Franchi;Franca;;;\n\
+         BDAY:1970-08-24\n\
+         EMAIL:franca@franchi.com\n\
+         TEL:3398889990\n\
+         LANG:de-CH\n\
+         END:VCARD\n",
+    ));
+
+    let friends = vcard_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(friends[0].locale, Locale::parse("de-CH"));
+}
+
+#[test]
+fn get_friends_with_a_date_time_birthday() {
+    let vcard_friends_gateway = VCardFriendsGateway::from_reader(Cursor::new(
+        "BEGIN:VCARD\n\
+         VERSION:3.0\n\
+         N:Germi;Mario;;;\n\
+         BDAY:19531015T231000Z\n\
+         EMAIL:mario@germi.com\n\
+         TEL:3396665559\n\
+         END:VCARD\n\
+         BEGIN:VCARD\n\
+         VERSION:3.0\n\
+         N:Franchi;Franca;;;\n\
+         BDAY:1970-08-24T08:30:00+02:00\n\
+         EMAIL:franca@franchi.com\n\
+         TEL:3398889990\n\
+         END:VCARD\n",
+    ));
+
+    let friends = vcard_friends_gateway.get_friends().unwrap();
+
+    assert_eq!(
+        friends
+            .iter()
+            .map(|friend| friend.birthdate)
+            .collect::<Vec<_>>(),
+        vec![
+            Birthdate::from_ymd_opt(1953, 10, 15).unwrap(),
+            Birthdate::from_ymd_opt(1970, 8, 24).unwrap()
+        ]
+    );
+}
+
+#[test]
+fn accept_contacts_with_an_email_or_a_phone_number_and_report_those_with_neither() {
+    let vcard_friends_gateway = VCardFriendsGateway::from_reader(Cursor::new(
+        "BEGIN:VCARD\n\
+         VERSION:3.0\n\
+         N:Franchi;Franca;;;\n\
+         BDAY:1970-08-24\n\
+         TEL:3398889990\n\
+         END:VCARD\n\
+         BEGIN:VCARD\n\
+         VERSION:3.0\n\
+         N:Germi;Mario;;;\n\
+         BDAY:1980-12-11\n\
+         EMAIL:mario@germi.com\n\
+         END:VCARD\n\
+         BEGIN:VCARD\n\
+         VERSION:3.0\n\
+         N:Doe;Mary;;;\n\
+         BDAY:1982-08-24\n\
+         END:VCARD\n",
+    ));
+
+    let (friends, report) = vcard_friends_gateway.get_friends_with_report().unwrap();
+
+    assert_eq!(
+        friends,
+        vec![
+            FriendData::new(
+                "Franca",
+                "Franchi",
+                NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+                "",
+                "3398889990"
+            ),
+            FriendData::new(
+                "Mario",
+                "Germi",
+                NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+                "mario@germi.com",
+                ""
+            )
+        ]
+    );
+    assert_eq!(
+        report.rejected_records,
+        vec![RejectedRecord::new(
+            13,
+            "BEGIN:VCARD\nVERSION:3.0\nN:Doe;Mary;;;\nBDAY:1982-08-24\nEND:VCARD",
+            InvalidFriendRecord::MissingField("EMAIL or TEL".to_string())
+        )]
+    );
+}
+
+#[test]
+fn get_friends_with_birthdays_in_the_configured_formats_reporting_the_matched_ones() {
+    let mut vcard_friends_gateway = VCardFriendsGateway::from_reader(Cursor::new(
+        "BEGIN:VCARD\n\
+         VERSION:3.0\n\
+         N:Franchi;Franca;;;\n\
+         BDAY:1970-08-24\n\
+         EMAIL:franca@franchi.com\n\
+         END:VCARD\n\
+         BEGIN:VCARD\n\
+         VERSION:3.0\n\
+         N:Germi;Mario;;;\n\
+         BDAY:11.12.1980\n\
+         EMAIL:mario@germi.com\n\
+         END:VCARD\n",
+    ));
+    vcard_friends_gateway.configure_birthdate_parser(BirthdateParser::new(vec![
+        DateFormat::iso(),
+        DateFormat::dotted(),
+    ]));
+
+    let (friends, report) = vcard_friends_gateway.get_friends_with_report().unwrap();
+
+    assert_eq!(
+        friends
+            .iter()
+            .map(|friend| friend.birthdate)
+            .collect::<Vec<_>>(),
+        vec![
+            Birthdate::from(NaiveDate::from_ymd_opt(1970, 8, 24).unwrap()),
+            Birthdate::from(NaiveDate::from_ymd_opt(1980, 12, 11).unwrap())
+        ]
+    );
+    assert_eq!(
+        report.matched_date_formats,
+        vec![
+            MatchedDateFormat::new(4, DateFormat::iso()),
+            MatchedDateFormat::new(10, DateFormat::dotted())
+        ]
+    );
+}
@@ -0,0 +1,92 @@
+use birthday_greetings_kata_rust::friends::friend_data::{FriendData, FriendId};
+use birthday_greetings_kata_rust::friends::friends_gateway::{FriendsGateway, FriendsGatewayError};
+use birthday_greetings_kata_rust::friends::in_memory_friends_gateway::InMemoryFriendsGateway;
+use birthday_greetings_kata_rust::friends::writable_friends_repository::WritableFriendsRepository;
+use chrono::NaiveDate;
+use std::rc::Rc;
+
+#[test]
+fn add_and_find_friends() {
+    let friends_gateway = Rc::new(InMemoryFriendsGateway::new());
+    let friends_repository = WritableFriendsRepository::new(friends_gateway.clone());
+
+    let franca_id = friends_repository.add(franca()).unwrap();
+    let mario_id = friends_repository.add(mario()).unwrap();
+
+    assert_ne!(franca_id, mario_id);
+    assert_eq!(
+        friends_repository.find(&mario_id),
+        Ok(Some(mario().with_id(mario_id.clone())))
+    );
+    assert_eq!(
+        friends_gateway.get_friends(),
+        Ok(vec![franca().with_id(franca_id), mario().with_id(mario_id)])
+    );
+}
+
+#[test]
+fn update_friends() {
+    let friends_repository = WritableFriendsRepository::new(Rc::new(InMemoryFriendsGateway::new()));
+    let id = friends_repository.add(franca()).unwrap();
+    let mut updated_franca = franca();
+    updated_franca.email = "franca@franchi.it".to_owned();
+
+    friends_repository
+        .update(&id, updated_franca.clone())
+        .unwrap();
+
+    assert_eq!(
+        friends_repository.find(&id),
+        Ok(Some(updated_franca.with_id(id.clone())))
+    );
+}
+
+#[test]
+fn remove_friends() {
+    let friends_repository = WritableFriendsRepository::new(Rc::new(InMemoryFriendsGateway::new()));
+    let franca_id = friends_repository.add(franca()).unwrap();
+    let mario_id = friends_repository.add(mario()).unwrap();
+
+    friends_repository.remove(&franca_id).unwrap();
+
+    assert_eq!(friends_repository.find(&franca_id), Ok(None));
+    assert_eq!(
+        friends_repository.get_all(),
+        Ok(vec![mario().with_id(mario_id)])
+    );
+}
+
+#[test]
+fn fail_to_update_or_remove_missing_friends() {
+    let friends_repository = WritableFriendsRepository::new(Rc::new(InMemoryFriendsGateway::new()));
+    let missing_id = FriendId::new("42");
+
+    assert_eq!(
+        friends_repository.update(&missing_id, franca()),
+        Err(FriendsGatewayError::FriendNotFound(missing_id.clone()))
+    );
+    assert_eq!(
+        friends_repository.remove(&missing_id),
+        Err(FriendsGatewayError::FriendNotFound(missing_id))
+    );
+}
+
+fn franca() -> FriendData {
+    FriendData::new(
+        "Franca",
+        "Franchi",
+        NaiveDate::from_ymd_opt(1970, 8, 24).unwrap(),
+        "franca@franchi.com",
+        "3398889990",
+    )
+}
+
+fn mario() -> FriendData {
+    FriendData::new(
+        "Mario",
+        "Germi",
+        NaiveDate::from_ymd_opt(1980, 12, 11).unwrap(),
+        "mario@germi.com",
+        "3396665559",
+    )
+}